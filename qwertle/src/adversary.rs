@@ -0,0 +1,108 @@
+use crate::{distance::Scorer, error::*, matrix::ScoreMatrix, recommend::*, score::Score, tree::*};
+
+// An adversarial ("evil") host, which never commits to an answer. Each guess gets the score shared by the most
+// answers still possible, so as many as possible stay alive. The player wins only when one answer is left and guessed.
+// Every score is consistent with every earlier one, so the game is still fair: some answer always matches them all.
+#[derive(Debug, Clone)]
+pub struct EvilHost<'a> {
+    scorer: Scorer,
+    candidates: Vec<&'a str>,
+}
+
+impl<'a> EvilHost<'a> {
+    pub fn new(scorer: &Scorer, answers: &[&'a str]) -> EvilHost<'a> {
+        EvilHost { scorer: scorer.clone(), candidates: answers.to_vec() }
+    }
+
+    // The answers still consistent with every score given.
+    pub fn candidates(&self) -> &[&'a str] {
+        &self.candidates
+    }
+
+    // Score a guess with the largest group of answers in its word_distance_map. When groups tie, avoid
+    // conceding the win, then pick the lowest score so games are repeatable. With no answers, the score is empty.
+    pub fn respond(&mut self, guess: &str) -> Score {
+        let map = self.scorer.score_map(guess, &self.candidates);
+
+        let (score, group) = map.into_iter()
+            .max_by(|(a, a_group), (b, b_group)| a_group.len().cmp(&b_group.len()).then_with(|| b.is_solved().cmp(&a.is_solved())).then_with(|| b.cmp(a)))
+            .unwrap_or_default();
+
+        self.candidates = group;
+        score
+    }
+}
+
+// The most guesses minimax play (ranking by worst case) needs to solve any answer after opening with 'opener',
+// with how many answers are solved on each guess. No host can do better against it, even an evil one.
+pub fn minimax_bound(matrix: &ScoreMatrix, opener: &str) -> Result<TreeStats, QwertleError> {
+    Ok(DecisionTree::build(matrix, opener, RankBy::WorstCase)?.stats())
+}
+
+// The guess which leaves the smallest worst case group among all the answers, the usual minimax opener.
+pub fn minimax_opener<'a>(matrix: &ScoreMatrix<'a>) -> Option<&'a str> {
+    let answers = (0..matrix.answers().len()).collect::<Vec<_>>();
+    rank_guesses(matrix, &answers, RankBy::WorstCase).first().map(|rating| rating.guess)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{*, answers::ANSWERS, solver::Solver};
+    use super::*;
+
+    #[test]
+    fn evil_host_keeps_largest_group() {
+        let mut host = EvilHost::new(Scorer::qwertle(), ANSWERS);
+
+        // 'apple' leaves at most four answers sharing a score (see map_and_cv), so the host keeps four
+        let score = host.respond("apple");
+        assert_eq!(4, host.candidates().len());
+        assert!(host.candidates().iter().all(|answer| word_distance("apple", answer).unwrap() == score));
+
+        // Guessing a candidate doesn't win while another answer could share its score
+        let guess = host.candidates()[0];
+        let score = host.respond(guess);
+        assert!(!score.is_solved() || host.candidates().len() == 1);
+    }
+
+    #[test]
+    fn evil_host_concedes_last_answer() {
+        let mut host = EvilHost::new(Scorer::qwertle(), &["vivid"]);
+        assert!(!host.respond("apple").is_solved());
+        assert!(host.respond("vivid").is_solved());
+        assert_eq!(vec!["vivid"], host.candidates());
+    }
+
+    #[test]
+    fn minimax_within_bound() {
+        let matrix = ScoreMatrix::build(Scorer::qwertle(), ANSWERS, ANSWERS).unwrap();
+        let opener = minimax_opener(&matrix).unwrap();
+        let bound = minimax_bound(&matrix, opener).unwrap();
+        assert_eq!(ANSWERS.len(), bound.answers);
+
+        // Minimax play against the evil host always wins within the bound
+        let mut host = EvilHost::new(Scorer::qwertle(), ANSWERS);
+        let mut solver = Solver::new(&matrix);
+        solver.set_rank_by(RankBy::WorstCase);
+
+        let mut guesses = 0;
+        let mut guess = opener.to_string();
+        loop {
+            let score = host.respond(&guess);
+            solver.add_turn(&guess, score).unwrap();
+            guesses += 1;
+
+            if score.is_solved() {
+                break;
+            }
+
+            guess = match solver.candidates()[..] {
+                [first, _] => first.to_string(),
+                _ => solver.recommend().unwrap().to_string(),
+            };
+        }
+
+        assert!(guesses <= bound.max_guesses);
+        assert_eq!(host.candidates(), solver.candidates());
+    }
+}
@@ -0,0 +1,177 @@
+use crate::{error::*, matrix::ScoreMatrix, recommend::*, score::Score, solver::Solver};
+
+// Solve several hidden answers at once, as in Dordle (two boards) or Quordle (four). Every guess is played on each
+// board, and each board scores it against its own answer. A board is finished once its answer is guessed, and
+// later guesses aren't scored on it. Each board narrows its own candidates with a single answer Solver.
+pub struct MultiSolver<'a> {
+    matrix: &'a ScoreMatrix<'a>,
+    boards: Vec<Solver<'a>>,
+    rank_by: RankBy,
+}
+
+impl<'a> MultiSolver<'a> {
+    // Start a game with 'boards' answers, where any answer in the matrix is possible on every board.
+    pub fn new(matrix: &'a ScoreMatrix<'a>, boards: usize) -> MultiSolver<'a> {
+        MultiSolver { matrix, boards: (0..boards).map(|_| Solver::new(matrix)).collect(), rank_by: RankBy::Entropy }
+    }
+
+    // Choose which measure recommendations are ranked by.
+    pub fn set_rank_by(&mut self, rank_by: RankBy) {
+        self.rank_by = rank_by;
+    }
+
+    pub fn boards(&self) -> &[Solver<'a>] {
+        &self.boards
+    }
+
+    // A board is finished once a guess scores all zeros on it.
+    pub fn is_finished(&self, board: usize) -> bool {
+        self.boards[board].history().last().is_some_and(|(_, score)| score.is_solved())
+    }
+
+    // The boards still being played, in order.
+    pub fn playing(&self) -> Vec<usize> {
+        (0..self.boards.len()).filter(|board| !self.is_finished(*board)).collect()
+    }
+
+    // The game is over once every board is finished.
+    pub fn is_done(&self) -> bool {
+        self.playing().is_empty()
+    }
+
+    // Record a guess with its score on each board still being played, in board order.
+    pub fn add_turn(&mut self, guess: &str, scores: &[Score]) -> Result<(), QwertleError> {
+        let playing = self.playing();
+        if scores.len() != playing.len() {
+            return Err(QwertleError::InvalidInput(format!("'{guess}' needs {} scores, one for each board still being played, but {} were given.", playing.len(), scores.len())));
+        }
+
+        // Check every score before changing any board, so a bad score leaves the game as it was
+        for score in scores.iter() {
+            validate_score(guess, score)?;
+        }
+
+        for (board, score) in playing.into_iter().zip(scores.iter()) {
+            self.boards[board].add_turn(guess, *score)?;
+        }
+
+        Ok(())
+    }
+
+    // Rate every guess against all the boards still being played, best first. The boards' answers are independent,
+    // so a guess's entropy is the sum of its entropy on each board, and its expected and worst case sizes are the
+    // total answers left across the boards. A guess is a candidate if it could be the answer on any board.
+    pub fn recommendations(&self) -> Vec<GuessRating<'a>> {
+        let playing = self.playing();
+
+        let mut is_candidate = vec![false; self.matrix.answers().len()];
+        for board in playing.iter() {
+            for answer in self.boards[*board].candidate_indices() {
+                is_candidate[*answer] = true;
+            }
+        }
+
+        let mut ratings = (0..self.matrix.guesses().len())
+            .map(|g| {
+                let guess = self.matrix.guesses()[g];
+                let candidate = self.matrix.answer_index(guess).is_some_and(|a| is_candidate[a]);
+                let mut total = GuessRating { guess, is_candidate: candidate, distinct: 1, entropy: 0.0, expected_size: 0.0, worst_case: 0 };
+
+                for board in playing.iter() {
+                    let rating = rate_matrix_guess(self.matrix, g, self.boards[*board].candidate_indices(), candidate);
+                    total.distinct = total.distinct.saturating_mul(rating.distinct.max(1));
+                    total.entropy += rating.entropy;
+                    total.expected_size += rating.expected_size;
+                    total.worst_case += rating.worst_case;
+                }
+
+                total
+            })
+            .collect::<Vec<_>>();
+
+        ratings.sort_by(|a, b| compare_ratings(a, b, self.rank_by));
+        ratings
+    }
+
+    // The answer on the first board still being played where only one answer is left, if any.
+    pub fn known_answer(&self) -> Option<&'a str> {
+        self.playing().into_iter().find_map(|board| match self.boards[board].candidates()[..] {
+            [answer] => Some(answer),
+            _ => None,
+        })
+    }
+
+    // Recommend the next guess. A board whose answer is known still needs that answer guessed, so guess it first
+    // (its scores on the other boards help too). Otherwise, guess the best ranked guess across the boards.
+    pub fn recommend(&self) -> Option<&'a str> {
+        if self.is_done() {
+            return None;
+        }
+
+        self.known_answer().or_else(|| self.recommendations().first().map(|rating| rating.guess))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{*, answers::ANSWERS, distance::Scorer};
+    use super::*;
+
+    fn play(solver: &mut MultiSolver, guess: &str, answers: &[&str]) {
+        let scores = solver.playing().iter().map(|board| word_distance(guess, answers[*board]).unwrap()).collect::<Vec<_>>();
+        solver.add_turn(guess, &scores).unwrap();
+    }
+
+    #[test]
+    fn boards_narrow_separately() {
+        let matrix = ScoreMatrix::build(Scorer::qwertle(), ANSWERS, ANSWERS).unwrap();
+        let mut solver = MultiSolver::new(&matrix, 2);
+        assert_eq!(vec![0, 1], solver.playing());
+
+        // Each board keeps the answers matching its own score
+        let answers = ["rigid", "vivid"];
+        play(&mut solver, "papal", &answers);
+        for (board, answer) in answers.iter().enumerate() {
+            let candidates = solver.boards()[board].candidates();
+            assert!(candidates.contains(answer));
+            assert!(candidates.iter().all(|c| word_distance("papal", c).unwrap() == word_distance("papal", answer).unwrap()));
+        }
+
+        // Once a board is guessed, it needs no more scores
+        play(&mut solver, "vivid", &answers);
+        assert!(solver.is_finished(1));
+        assert_eq!(vec![0], solver.playing());
+
+        let score = word_distance("apple", "rigid").unwrap();
+        assert!(matches!(solver.add_turn("apple", &[score, score]), Err(QwertleError::InvalidInput(_))));
+        assert!(solver.add_turn("apple", &["123".parse().unwrap()]).is_err());
+        assert_eq!(2, solver.boards()[0].history().len());
+
+        play(&mut solver, "rigid", &answers);
+        assert!(solver.is_done());
+        assert_eq!(None, solver.recommend());
+    }
+
+    #[test]
+    fn recommend_across_boards() {
+        let matrix = ScoreMatrix::build(Scorer::qwertle(), ANSWERS, ANSWERS).unwrap();
+        let mut solver = MultiSolver::new(&matrix, 2);
+
+        // With every answer possible on both boards, the entropy is twice the single board entropy
+        let single = rank_guesses(&matrix, &(0..ANSWERS.len()).collect::<Vec<_>>(), RankBy::Entropy);
+        let multi = solver.recommendations();
+        assert_eq!(single[0].guess, multi[0].guess);
+        assert!((multi[0].entropy - 2.0 * single[0].entropy).abs() < 1e-9);
+
+        // Play until both boards are solved; the known answer on a board is guessed as soon as it's found
+        let answers = ["gaunt", "taunt"];
+        let mut guesses = 0;
+        while let Some(guess) = solver.recommend() {
+            play(&mut solver, guess, &answers);
+            guesses += 1;
+            assert!(guesses <= 7);
+        }
+
+        assert!(solver.boards().iter().zip(answers.iter()).all(|(board, answer)| board.candidates() == vec![*answer]));
+    }
+}
@@ -0,0 +1,333 @@
+use std::{fmt, str::FromStr};
+use serde::{Serialize, Serializer};
+use crate::{error::QwertleError, score::*};
+
+// Read scores from tile colors, instead of translating the gradient to digits by eye.
+// A ColorTable holds the color of each distance digit. The default is the green to yellow to red gradient
+// drawn by the terminal modes (see tui::GRADIENT); QWERTLE's own shades vary by theme and screen, so
+// calibrate by sampling real tiles into a table file with 'digit color' per line (ex: '0 #6aaa64').
+
+// How far (in RGB units) a color may be from the nearest table color and still count as a tile of that color.
+// Neighboring gradient shades are about 40 apart, so this allows for shading and compression noise.
+const MATCH_TOLERANCE: u32 = 48;
+
+// An image column is part of a tile if at least this share of its pixels are tile colors.
+// Letters are drawn over the middle of each tile, so a tile column is never entirely tile colored.
+const TILE_COLUMN_SHARE: f64 = 0.25;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Rgb {
+    pub const fn new(r: u8, g: u8, b: u8) -> Rgb {
+        Rgb { r, g, b }
+    }
+
+    // The squared distance between two colors, treating red, green, and blue as coordinates.
+    pub fn distance_squared(&self, other: &Rgb) -> u32 {
+        let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2) as u32;
+        d(self.r, other.r) + d(self.g, other.g) + d(self.b, other.b)
+    }
+}
+
+impl fmt::Display for Rgb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+// Colors are written in JSON as their hex form (ex: "#87ff00").
+impl Serialize for Rgb {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+// Parse a color as hex ('#87ff00' or '87ff00') or as red, green, and blue values ('135,255,0').
+impl FromStr for Rgb {
+    type Err = QwertleError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim();
+        let invalid = || QwertleError::InvalidInput(format!("Color '{text}' must be hex (#87ff00) or 'red,green,blue' (135,255,0)."));
+
+        let parts = if text.contains(',') {
+            text.split(',').map(|part| part.trim().parse::<u8>().ok()).collect::<Vec<_>>()
+        } else {
+            let hex = text.strip_prefix('#').unwrap_or(text);
+            if hex.len() != 6 || !hex.is_ascii() {
+                return Err(invalid());
+            }
+
+            (0..3).map(|i| u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).ok()).collect()
+        };
+
+        match parts[..] {
+            [Some(r), Some(g), Some(b)] => Ok(Rgb::new(r, g, b)),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+// The tile color for each distance digit. A digit may have several colors (ex: samples from different screenshots).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorTable {
+    colors: Vec<(Rgb, u8)>,
+}
+
+impl ColorTable {
+    // The gradient used by the terminal modes: the xterm colors in tui::GRADIENT.
+    pub fn qwertle() -> ColorTable {
+        const GRADIENT: [Rgb; 10] = [
+            Rgb::new(0x00, 0xff, 0x00), Rgb::new(0x5f, 0xff, 0x00), Rgb::new(0x87, 0xff, 0x00), Rgb::new(0xaf, 0xff, 0x00), Rgb::new(0xd7, 0xff, 0x00),
+            Rgb::new(0xff, 0xff, 0x00), Rgb::new(0xff, 0xd7, 0x00), Rgb::new(0xff, 0xaf, 0x00), Rgb::new(0xff, 0x87, 0x00), Rgb::new(0xff, 0x00, 0x00),
+        ];
+
+        ColorTable { colors: GRADIENT.iter().zip(0..).map(|(color, digit)| (*color, digit)).collect() }
+    }
+
+    // Parse a color table, one 'digit color' per line (ex: '3 #afff00'). Blank lines and lines starting with '#' are skipped.
+    pub fn parse(text: &str) -> Result<ColorTable, QwertleError> {
+        let mut colors = Vec::new();
+
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let invalid = |reason: String| QwertleError::InvalidInput(format!("Line {}: {reason}", number + 1));
+            match line.split_once(char::is_whitespace) {
+                Some((digit, color)) => {
+                    let digit = digit.parse::<u8>().map_err(|_| invalid(format!("'{digit}' is not a distance.")))?;
+                    let color = color.parse::<Rgb>().map_err(|error| invalid(error.to_string()))?;
+                    colors.push((color, digit));
+                }
+                None => return Err(invalid(format!("should be 'digit color', not '{line}'."))),
+            }
+        }
+
+        if colors.is_empty() {
+            return Err(QwertleError::InvalidInput("The color table has no colors.".to_string()));
+        }
+
+        Ok(ColorTable { colors })
+    }
+
+    // The digit of the table color closest to a color, if it's close enough to be a tile.
+    pub fn digit(&self, color: Rgb) -> Option<u8> {
+        self.colors.iter()
+            .map(|(table_color, digit)| (table_color.distance_squared(&color), *digit))
+            .min()
+            .filter(|(distance, _)| *distance <= MATCH_TOLERANCE * MATCH_TOLERANCE)
+            .map(|(_, digit)| digit)
+    }
+}
+
+impl Default for ColorTable {
+    fn default() -> Self {
+        ColorTable::qwertle()
+    }
+}
+
+// A decoded image, row by row.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<Rgb>,
+}
+
+impl Image {
+    // Decode a PNG of any color type. Transparency is ignored.
+    pub fn from_png(bytes: &[u8]) -> Result<Image, QwertleError> {
+        let invalid = |error: png::DecodingError| QwertleError::InvalidInput(format!("Unable to decode PNG: {error}"));
+
+        let mut decoder = png::Decoder::new(bytes);
+        decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+        let mut reader = decoder.read_info().map_err(invalid)?;
+
+        let mut buffer = vec![0; reader.output_buffer_size()];
+        let frame = reader.next_frame(&mut buffer).map_err(invalid)?;
+        let samples = frame.color_type.samples();
+
+        let pixels = buffer[..frame.buffer_size()]
+            .chunks_exact(frame.line_size)
+            .flat_map(|line| line[..frame.width as usize * samples].chunks_exact(samples))
+            .map(|pixel| match pixel {
+                [gray] | [gray, _] => Rgb::new(*gray, *gray, *gray),
+                [r, g, b, ..] => Rgb::new(*r, *g, *b),
+                _ => unreachable!(),
+            })
+            .collect();
+
+        Ok(Image { width: frame.width as usize, height: frame.height as usize, pixels })
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> Rgb {
+        self.pixels[y * self.width + x]
+    }
+}
+
+// A tile read from a color or image: its color (the average of its tile colored pixels) and distance digit.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Tile {
+    pub color: Rgb,
+    pub digit: u8,
+}
+
+// The digit for each color given, in order. Every color must be close to a table color.
+pub fn read_colors(table: &ColorTable, colors: &[Rgb]) -> Result<Vec<Tile>, QwertleError> {
+    colors.iter()
+        .map(|color| match table.digit(*color) {
+            Some(digit) => Ok(Tile { color: *color, digit }),
+            None => Err(QwertleError::InvalidInput(format!("Color {color} isn't close to any tile color. Add it to a color table with --colors."))),
+        })
+        .collect()
+}
+
+// Find the tiles in a screenshot of one guess row, left to right.
+// Tiles are runs of columns which are mostly tile colored, with background between them. Slivers under half
+// the width of the widest tile (ex: blended edges) are ignored. Each tile's digit is the one most of its pixels match.
+pub fn read_image(table: &ColorTable, image: &Image) -> Vec<Tile> {
+    let digits = image.pixels.iter().map(|pixel| table.digit(*pixel)).collect::<Vec<_>>();
+
+    let is_tile_column = (0..image.width)
+        .map(|x| (0..image.height).filter(|y| digits[y * image.width + x].is_some()).count() as f64 >= image.height as f64 * TILE_COLUMN_SHARE)
+        .collect::<Vec<_>>();
+
+    let mut runs = Vec::new();
+    let mut start = None;
+    for (x, is_tile) in is_tile_column.iter().chain([false].iter()).enumerate() {
+        match (start, *is_tile) {
+            (None, true) => start = Some(x),
+            (Some(left), false) => {
+                runs.push(left..x);
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    let widest = runs.iter().map(|run| run.len()).max().unwrap_or(0);
+    runs.iter()
+        .filter(|run| run.len() * 2 >= widest)
+        .map(|run| {
+            let mut votes = [0usize; 256];
+            let mut sums = [[0u64; 3]; 256];
+
+            for y in 0..image.height {
+                for x in run.clone() {
+                    if let Some(digit) = digits[y * image.width + x] {
+                        let pixel = image.pixel(x, y);
+                        votes[digit as usize] += 1;
+                        sums[digit as usize][0] += pixel.r as u64;
+                        sums[digit as usize][1] += pixel.g as u64;
+                        sums[digit as usize][2] += pixel.b as u64;
+                    }
+                }
+            }
+
+            let digit = (0..256).max_by_key(|d| (votes[*d], std::cmp::Reverse(*d))).unwrap_or(0);
+            let average = |sum: u64| (sum / votes[digit].max(1) as u64) as u8;
+            Tile { color: Rgb::new(average(sums[digit][0]), average(sums[digit][1]), average(sums[digit][2])), digit: digit as u8 }
+        })
+        .collect()
+}
+
+// The score shown by a row of tiles.
+pub fn tiles_to_score(tiles: &[Tile]) -> Result<Score, QwertleError> {
+    if tiles.is_empty() {
+        return Err(QwertleError::InvalidInput("No tiles were found. Check the image is one guess row, or calibrate the tile colors with --colors.".to_string()));
+    }
+
+    if tiles.len() > MAX_WORD_LENGTH {
+        return Err(QwertleError::InvalidInput(format!("Found {} tiles, but words have at most {MAX_WORD_LENGTH} letters.", tiles.len())));
+    }
+
+    Score::new(&tiles.iter().map(|tile| tile.digit).collect::<Vec<_>>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIGHT_ROW: &[u8] = include_bytes!("../tests/fixtures/row-42521.png");
+    const DARK_ROW: &[u8] = include_bytes!("../tests/fixtures/row-dark-503659.png");
+    const DARK_COLORS: &str = include_str!("../tests/fixtures/dark-colors.txt");
+
+    #[test]
+    fn parse_colors() {
+        assert_eq!(Rgb::new(0x87, 0xff, 0x00), "#87FF00".parse().unwrap());
+        assert_eq!(Rgb::new(0x87, 0xff, 0x00), "87ff00".parse().unwrap());
+        assert_eq!(Rgb::new(135, 255, 0), " 135, 255,0 ".parse().unwrap());
+        assert_eq!("#87ff00", Rgb::new(135, 255, 0).to_string());
+
+        assert!("#87ff0".parse::<Rgb>().is_err());
+        assert!("#87ffgg".parse::<Rgb>().is_err());
+        assert!("135,256,0".parse::<Rgb>().is_err());
+        assert!("#87ff0é".parse::<Rgb>().is_err());
+    }
+
+    #[test]
+    fn color_digits() {
+        let table = ColorTable::qwertle();
+        assert_eq!(Some(0), table.digit(Rgb::new(0, 255, 0)));
+        assert_eq!(Some(5), table.digit(Rgb::new(250, 250, 10)));
+        assert_eq!(Some(9), table.digit(Rgb::new(230, 20, 10)));
+        assert_eq!(None, table.digit(Rgb::new(255, 255, 255)));
+        assert_eq!(None, table.digit(Rgb::new(0, 0, 0)));
+
+        let tiles = read_colors(&table, &["#d7ff00".parse().unwrap(), "#87ff00".parse().unwrap()]).unwrap();
+        assert_eq!("42", tiles_to_score(&tiles).unwrap().to_string());
+        assert!(read_colors(&table, &[Rgb::new(40, 40, 200)]).is_err());
+    }
+
+    #[test]
+    fn parse_table() {
+        let table = ColorTable::parse(DARK_COLORS).unwrap();
+        assert_eq!(Some(0), table.digit("#1f8a70".parse().unwrap()));
+        assert_eq!(None, table.digit("#00ff00".parse().unwrap()));
+
+        // Several samples for one digit are allowed
+        let table = ColorTable::parse("0 #6aaa64\n0 6cab66\n\n# yellow\n5 201,180,88\n").unwrap();
+        assert_eq!(Some(5), table.digit(Rgb::new(200, 180, 90)));
+
+        assert!(ColorTable::parse("").is_err());
+        assert!(ColorTable::parse("x #6aaa64").is_err());
+        assert_eq!("Line 2: should be 'digit color', not '6aaa64'.", ColorTable::parse("0 #6aaa64\n6aaa64").unwrap_err().to_string());
+    }
+
+    #[test]
+    fn read_light_row() {
+        let image = Image::from_png(LIGHT_ROW).unwrap();
+        assert_eq!((360, 80), (image.width, image.height));
+
+        let tiles = read_image(&ColorTable::qwertle(), &image);
+        assert_eq!("42521", tiles_to_score(&tiles).unwrap().to_string());
+        assert_eq!(Tile { color: "#d7ff00".parse().unwrap(), digit: 4 }, tiles[0]);
+    }
+
+    #[test]
+    fn read_calibrated_row() {
+        // A dark theme with different colors, blended tile edges, and noise: the default gradient finds nothing
+        let image = Image::from_png(DARK_ROW).unwrap();
+        assert!(tiles_to_score(&read_image(&ColorTable::qwertle(), &image)).is_err());
+
+        let tiles = read_image(&ColorTable::parse(DARK_COLORS).unwrap(), &image);
+        assert_eq!("503659", tiles_to_score(&tiles).unwrap().to_string());
+
+        // Tile colors average out the noise
+        assert!(tiles[1].color.distance_squared(&"#1f8a70".parse().unwrap()) < 9);
+    }
+
+    #[test]
+    fn invalid_png() {
+        assert!(Image::from_png(b"not a png").is_err());
+        assert!(Image::from_png(&LIGHT_ROW[..100]).is_err());
+    }
+}
@@ -0,0 +1,130 @@
+use std::{fmt, str::FromStr, time::{SystemTime, UNIX_EPOCH}};
+use serde::{Serialize, Serializer};
+use crate::error::QwertleError;
+
+// A calendar date (proleptic Gregorian, UTC), for the daily answer schedule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Date {
+    pub fn new(year: i32, month: u32, day: u32) -> Option<Date> {
+        if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+            return None;
+        }
+
+        Some(Date { year, month, day })
+    }
+
+    // Today's date in UTC.
+    pub fn today() -> Date {
+        let seconds = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        Date::from_days((seconds / 86_400) as i64)
+    }
+
+    // Days since 1970-01-01 (negative before it).
+    pub fn days(&self) -> i64 {
+        // From Howard Hinnant's 'days_from_civil': count from March so the leap day ends the year
+        let year = if self.month <= 2 { self.year - 1 } else { self.year } as i64;
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let month = self.month as i64;
+        let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + self.day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    // The date a number of days after 1970-01-01.
+    pub fn from_days(days: i64) -> Date {
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days - era * 146_097;
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+
+        let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+        let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 } as u32;
+        let year = (year_of_era + era * 400) as i32 + if month <= 2 { 1 } else { 0 };
+
+        Date { year, month, day }
+    }
+
+    pub fn add_days(&self, days: i64) -> Date {
+        Date::from_days(self.days() + days)
+    }
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    let leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+
+    match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+// Dates are written in JSON as YYYY-MM-DD.
+impl Serialize for Date {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+// Parse a date as YYYY-MM-DD.
+impl FromStr for Date {
+    type Err = QwertleError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let parts = text.trim().split('-').map(|part| part.parse::<u32>().ok()).collect::<Vec<_>>();
+
+        match parts[..] {
+            [Some(year), Some(month), Some(day)] => Date::new(year as i32, month, day).ok_or(QwertleError::InvalidInput(format!("'{text}' is not a valid date."))),
+            _ => Err(QwertleError::InvalidInput(format!("Date '{text}' must be YYYY-MM-DD."))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_round_trip() {
+        assert_eq!(0, Date::new(1970, 1, 1).unwrap().days());
+        assert_eq!(18_797, Date::new(2021, 6, 19).unwrap().days());
+        assert_eq!(-1, Date::new(1969, 12, 31).unwrap().days());
+
+        for days in (-800_000..800_000).step_by(997) {
+            assert_eq!(days, Date::from_days(days).days());
+        }
+
+        let leap_day = Date::new(2024, 2, 29).unwrap();
+        assert_eq!(Date::new(2024, 3, 1).unwrap(), leap_day.add_days(1));
+        assert_eq!(Date::new(2023, 3, 1).unwrap(), leap_day.add_days(-365));
+    }
+
+    #[test]
+    fn parse_dates() {
+        let date = "2022-02-03".parse::<Date>().unwrap();
+        assert_eq!(Date { year: 2022, month: 2, day: 3 }, date);
+        assert_eq!("2022-02-03", date.to_string());
+
+        assert!("2023-02-29".parse::<Date>().is_err());
+        assert!("2024-13-01".parse::<Date>().is_err());
+        assert!("2024/01/01".parse::<Date>().is_err());
+        assert!("today".parse::<Date>().is_err());
+    }
+}
@@ -0,0 +1,172 @@
+use std::{collections::HashSet, fs, path::Path};
+use crate::error::*;
+
+// Default word lists, compiled into the binary.
+//  answers.txt: The original Wordle answers (2,315 words).
+//  guesses.txt: Other words Wordle accepts as guesses, but which are never answers (10,657 words).
+pub const EMBEDDED_ANSWERS: &str = include_str!("../data/answers.txt");
+pub const EMBEDDED_GUESSES: &str = include_str!("../data/guesses.txt");
+
+// The words which may be the answer, and the (larger) set of words which may be guessed.
+// Every answer is also an allowed guess. Answers must all be the same length, and only guesses of
+// that length are kept, so a custom list of six letter answers uses only six letter guesses.
+pub struct Dictionary {
+    answers: Vec<String>,
+    guesses: Vec<String>,
+}
+
+impl Dictionary {
+    // Build a Dictionary from word lists with one word per line. Repeated answers are kept once, so they aren't
+    // counted twice when ranking guesses. Fails if the answers aren't all the same length.
+    pub fn from_text(answers: &str, guesses: &str) -> Result<Dictionary, QwertleError> {
+        let mut seen = HashSet::new();
+        let mut answers = parse_words(answers);
+        answers.retain(|answer| seen.insert(answer.clone()));
+
+        if let Some(first) = answers.first() {
+            for answer in answers.iter() {
+                validate_word_length(answer, first.len()).map_err(|error| QwertleError::InvalidInput(format!("Answers must all be the same length: {error}")))?;
+            }
+        }
+
+        let mut all_guesses = parse_words(guesses);
+        if let Some(length) = word_length(&answers) {
+            all_guesses.retain(|guess| guess.len() == length);
+        }
+
+        all_guesses.extend(answers.iter().cloned());
+        all_guesses.sort();
+        all_guesses.dedup();
+
+        Ok(Dictionary { answers, guesses: all_guesses })
+    }
+
+    // The compiled-in Wordle answer and guess lists.
+    pub fn embedded() -> Dictionary {
+        Dictionary::from_text(EMBEDDED_ANSWERS, EMBEDDED_GUESSES).unwrap()
+    }
+
+    // Load either word list from a file, using the embedded list for any path not provided.
+    // Every word in a file must be letters a-z, since words are scored letter by letter.
+    pub fn load(answers_path: Option<&Path>, guesses_path: Option<&Path>) -> Result<Dictionary, QwertleError> {
+        let answers = match answers_path {
+            Some(path) => read_words(path)?,
+            None => EMBEDDED_ANSWERS.to_string(),
+        };
+
+        let guesses = match guesses_path {
+            Some(path) => read_words(path)?,
+            None => EMBEDDED_GUESSES.to_string(),
+        };
+
+        Dictionary::from_text(&answers, &guesses)
+    }
+
+    pub fn answers(&self) -> Vec<&str> {
+        self.answers.iter().map(|w| w.as_str()).collect()
+    }
+
+    pub fn guesses(&self) -> Vec<&str> {
+        self.guesses.iter().map(|w| w.as_str()).collect()
+    }
+
+    // The length of every answer, or None if there are no answers.
+    pub fn word_length(&self) -> Option<usize> {
+        word_length(&self.answers)
+    }
+
+    pub fn is_answer(&self, word: &str) -> bool {
+        self.answers.iter().any(|w| w.eq_ignore_ascii_case(word))
+    }
+
+    pub fn is_allowed_guess(&self, word: &str) -> bool {
+        let word = word.to_ascii_lowercase();
+        self.guesses.binary_search(&word).is_ok()
+    }
+}
+
+fn word_length(words: &[String]) -> Option<usize> {
+    let length = words.first()?.len();
+    words.iter().all(|w| w.len() == length).then_some(length)
+}
+
+// Read a word list file, checking every word is valid.
+fn read_words(path: &Path) -> Result<String, QwertleError> {
+    let path_text = path.display().to_string();
+    let text = fs::read_to_string(path).map_err(|e| QwertleError::Io { path: path_text.clone(), message: e.to_string() })?;
+
+    for (number, line) in text.lines().enumerate() {
+        let word = line.trim();
+        if !word.is_empty() {
+            validate_word(word).map_err(|error| QwertleError::InvalidInput(format!("Line {} of '{path_text}': {error}", number + 1)))?;
+        }
+    }
+
+    Ok(text)
+}
+
+// Split a word list into lowercase words, skipping blank lines.
+fn parse_words(text: &str) -> Vec<String> {
+    text.lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .map(|line| line.to_ascii_lowercase())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answers::ANSWERS;
+    use super::*;
+
+    #[test]
+    fn embedded_dictionary() {
+        let dictionary = Dictionary::embedded();
+        assert_eq!(ANSWERS, dictionary.answers().as_slice());
+        assert_eq!(2315 + 10657, dictionary.guesses().len());
+        assert_eq!(Some(5), dictionary.word_length());
+
+        assert!(dictionary.is_answer("papal"));
+        assert!(dictionary.is_allowed_guess("papal"));
+
+        assert!(!dictionary.is_answer("aahed"));
+        assert!(dictionary.is_allowed_guess("AAHED"));
+
+        assert!(!dictionary.is_allowed_guess("qwert"));
+    }
+
+    #[test]
+    fn dictionary_from_text() {
+        let dictionary = Dictionary::from_text("Hello\r\nworld\n\n", "zebra\nhello\n").unwrap();
+        assert_eq!(vec!["hello", "world"], dictionary.answers());
+        assert_eq!(vec!["hello", "world", "zebra"], dictionary.guesses());
+
+        // Only guesses the same length as the answers are kept
+        let dictionary = Dictionary::from_text("papers\ntapers", "zebra\nwipers\n").unwrap();
+        assert_eq!(Some(6), dictionary.word_length());
+        assert_eq!(vec!["papers", "tapers", "wipers"], dictionary.guesses());
+
+        // Repeated answers are kept once, in the order first seen
+        let dictionary = Dictionary::from_text("world\nhello\nWORLD\nhello\n", "").unwrap();
+        assert_eq!(vec!["world", "hello"], dictionary.answers());
+
+        // Answers of different lengths are rejected
+        let error = Dictionary::from_text("papers\nzebra", "tapers\n").err().unwrap();
+        assert_eq!("Answers must all be the same length: 'zebra' has 5 letters, but should have 6.", error.to_string());
+    }
+
+    #[test]
+    fn load_errors() {
+        let missing = std::env::temp_dir().join("qwertle-no-such-word-list.txt");
+        assert!(matches!(Dictionary::load(Some(&missing), None), Err(QwertleError::Io { .. })));
+
+        let path = std::env::temp_dir().join(format!("qwertle-dictionary-test-{}.txt", std::process::id()));
+        fs::write(&path, "papal\n\ndon't\n").unwrap();
+        let error = Dictionary::load(Some(&path), None).err().unwrap();
+        assert_eq!(format!("Line 3 of '{}': 'don't' contains '''; words can only use the letters a-z.", path.display()), error.to_string());
+
+        fs::write(&path, "papal\nPAINT\n").unwrap();
+        assert_eq!(vec!["papal", "paint"], Dictionary::load(Some(&path), None).unwrap().answers());
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,357 @@
+use std::{collections::VecDeque, fmt::Debug, sync::{Arc, OnceLock}};
+use crate::{error::*, keyboard::*, score::Score};
+
+// How to turn the position of two keys into the distance digit shown on a tile.
+// Digits may go above 9 (a small unit, or many hops), so a score digit can be any u8.
+// Only a letter and itself may be 0 apart; Scorer::new rejects models that give 0 to different letters.
+pub trait DistanceModel: Debug + Send + Sync {
+    fn quantized_distance(&self, layout: &KeyboardLayout, left: char, right: char) -> u8;
+}
+
+// Straight-line pixel distance, rounded to the nearest unit. QWERTLE uses 50 px units,
+// the distance away of one letter directly left or right.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Euclidean {
+    pub unit: f64,
+}
+
+// Horizontal plus vertical pixel distance, rounded to the nearest unit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Manhattan {
+    pub unit: f64,
+}
+
+// The number of key-to-key hops between letters, where keys are neighbors if their
+// pixel distance is within 'neighbor_distance'. The default includes keys diagonally above and below.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyHops {
+    pub neighbor_distance: f64,
+}
+
+// Non-linear colour buckets: the digit is the index of the first limit the pixel distance is within,
+// or the number of limits if the distance is beyond all of them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Buckets {
+    pub limits: Vec<f64>,
+}
+
+impl Default for Euclidean {
+    fn default() -> Self {
+        Euclidean { unit: 50.0 }
+    }
+}
+
+impl Default for Manhattan {
+    fn default() -> Self {
+        Manhattan { unit: 50.0 }
+    }
+}
+
+impl Default for KeyHops {
+    fn default() -> Self {
+        KeyHops { neighbor_distance: 85.0 }
+    }
+}
+
+impl DistanceModel for Euclidean {
+    fn quantized_distance(&self, layout: &KeyboardLayout, left: char, right: char) -> u8 {
+        (layout.distance_between_letters(left, right) / self.unit).round() as u8
+    }
+}
+
+impl DistanceModel for Manhattan {
+    fn quantized_distance(&self, layout: &KeyboardLayout, left: char, right: char) -> u8 {
+        let (x1, y1) = layout.positions[letter_index(left)];
+        let (x2, y2) = layout.positions[letter_index(right)];
+
+        let distance = x1.abs_diff(x2) as f64 + y1.abs_diff(y2) as f64;
+        (distance / self.unit).round() as u8
+    }
+}
+
+impl DistanceModel for KeyHops {
+    fn quantized_distance(&self, layout: &KeyboardLayout, left: char, right: char) -> u8 {
+        let (from, to) = (letter_index(left), letter_index(right));
+
+        // Breadth-first search outward from the left key until reaching the right key
+        let mut hops = [u8::MAX; 26];
+        let mut queue = VecDeque::new();
+        hops[from] = 0;
+        queue.push_back(from);
+
+        while let Some(current) = queue.pop_front() {
+            if current == to {
+                return hops[current];
+            }
+
+            let current_letter = (b'a' + current as u8) as char;
+            for (next, next_letter) in ('a'..='z').enumerate() {
+                if hops[next] == u8::MAX && layout.distance_between_letters(current_letter, next_letter) <= self.neighbor_distance {
+                    hops[next] = hops[current] + 1;
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        hops[to]
+    }
+}
+
+impl DistanceModel for Buckets {
+    fn quantized_distance(&self, layout: &KeyboardLayout, left: char, right: char) -> u8 {
+        let distance = layout.distance_between_letters(left, right);
+        self.limits.iter().position(|limit| distance <= *limit).unwrap_or(self.limits.len()) as u8
+    }
+}
+
+// Parse a distance model: 'euclidean', 'manhattan', or 'hops' with an optional ':<pixels>' unit or neighbor distance,
+// or 'buckets:<limit>,<limit>,...' with the pixel limit of each digit.
+pub fn parse_model(text: &str) -> Result<Arc<dyn DistanceModel>, QwertleError> {
+    let text = text.to_ascii_lowercase();
+    let (name, argument) = match text.split_once(':') {
+        Some((name, argument)) => (name, Some(argument)),
+        None => (text.as_str(), None),
+    };
+
+    let parse_number = |argument: &str| -> Result<f64, QwertleError> {
+        match argument.trim().parse::<f64>() {
+            Ok(value) if value > 0.0 => Ok(value),
+            _ => Err(QwertleError::InvalidInput(format!("Distance model '{text}' needs a positive number, not '{argument}'."))),
+        }
+    };
+
+    match (name, argument) {
+        ("euclidean", None) => Ok(Arc::new(Euclidean::default())),
+        ("euclidean", Some(unit)) => Ok(Arc::new(Euclidean { unit: parse_number(unit)? })),
+        ("manhattan", None) => Ok(Arc::new(Manhattan::default())),
+        ("manhattan", Some(unit)) => Ok(Arc::new(Manhattan { unit: parse_number(unit)? })),
+        ("hops", None) => Ok(Arc::new(KeyHops::default())),
+        ("hops", Some(neighbor)) => Ok(Arc::new(KeyHops { neighbor_distance: parse_number(neighbor)? })),
+        ("buckets", Some(limits)) => {
+            let limits = limits.split(',').map(parse_number).collect::<Result<Vec<_>, _>>()?;
+            if limits.windows(2).any(|pair| pair[0] >= pair[1]) {
+                return Err(QwertleError::InvalidInput(format!("Distance model '{text}' limits must be increasing.")));
+            }
+
+            Ok(Arc::new(Buckets { limits }))
+        }
+        _ => Err(QwertleError::InvalidInput(format!("Unknown distance model '{text}'. Use 'euclidean', 'manhattan', 'hops', or 'buckets:<limits>'."))),
+    }
+}
+
+// A keyboard layout and distance model together, used to score guesses.
+// The quantized distance between every pair of letters is computed once up front, since
+// scoring is done millions of times when comparing every guess against every answer.
+#[derive(Debug, Clone)]
+pub struct Scorer {
+    layout: KeyboardLayout,
+    model: Arc<dyn DistanceModel>,
+    table: [[u8; 26]; 26],
+}
+
+impl Scorer {
+    // Fails if the model puts two different letters 0 apart: a score of all zeros would then match more than one
+    // word, so 'solved' wouldn't mean the answer was found.
+    pub fn new(layout: KeyboardLayout, model: Arc<dyn DistanceModel>) -> Result<Scorer, QwertleError> {
+        let mut table = [[0u8; 26]; 26];
+
+        for (i, left) in ('a'..='z').enumerate() {
+            for (j, right) in ('a'..='z').enumerate() {
+                table[i][j] = model.quantized_distance(&layout, left, right);
+
+                if i != j && table[i][j] == 0 {
+                    return Err(QwertleError::InvalidInput(format!("Distance model {model:?} puts '{left}' and '{right}' 0 apart on the '{}' layout; only a letter and itself may be 0 apart.", layout.name)));
+                }
+            }
+        }
+
+        Ok(Scorer { layout, model, table })
+    }
+
+    pub fn layout(&self) -> &KeyboardLayout {
+        &self.layout
+    }
+
+    pub fn model(&self) -> &dyn DistanceModel {
+        self.model.as_ref()
+    }
+
+    // The quantized distance between each pair of letters, indexed by letter ('a' = 0).
+    pub fn table(&self) -> &[[u8; 26]; 26] {
+        &self.table
+    }
+
+    // The largest distance between any two letters: the number of columns a table of letters by distance needs, after 0.
+    pub fn max_distance(&self) -> u8 {
+        self.table.iter().flatten().copied().max().unwrap_or(0)
+    }
+
+    // The scoring used by QWERTLE: the QWERTY keyboard with 50 px Euclidean units.
+    pub fn qwertle() -> &'static Scorer {
+        static QWERTLE: OnceLock<Scorer> = OnceLock::new();
+        QWERTLE.get_or_init(|| Scorer::new(QWERTY, Arc::new(Euclidean::default())).unwrap())
+    }
+}
+
+impl Default for Scorer {
+    fn default() -> Self {
+        Scorer::qwertle().clone()
+    }
+}
+
+// A score seen in a real game: the guess, the answer, and the score shown.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedScore {
+    pub guess: String,
+    pub answer: String,
+    pub score: Score,
+}
+
+// Parse recorded scores, one 'guess answer score' per line. Blank lines and lines starting with '#' are skipped.
+pub fn parse_recorded_scores(text: &str) -> Result<Vec<RecordedScore>, QwertleError> {
+    let mut recorded = Vec::new();
+
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let in_line = |error: QwertleError| QwertleError::InvalidInput(format!("Line {}: {error}", number + 1));
+        match line.split_whitespace().collect::<Vec<_>>()[..] {
+            [guess, answer, score] => {
+                let guess = validate_word(guess).map_err(in_line)?;
+                let answer = validate_word_length(answer, guess.len()).map_err(in_line)?;
+                let score = score.parse::<Score>().map_err(in_line)?;
+                validate_score(&guess, &score).map_err(in_line)?;
+                recorded.push(RecordedScore { guess, answer, score });
+            }
+            _ => return Err(QwertleError::InvalidInput(format!("Line {} should be 'guess answer score', not '{line}'.", number + 1))),
+        }
+    }
+
+    Ok(recorded)
+}
+
+// Find the recorded scores which a Scorer doesn't reproduce, with the score it computes instead.
+pub fn find_mismatches<'a>(scorer: &Scorer, recorded: &'a [RecordedScore]) -> Vec<(&'a RecordedScore, Score)> {
+    recorded.iter()
+        .map(|r| (r, scorer.score_words(&r.guess, &r.answer)))
+        .filter(|(r, computed)| r.score != *computed)
+        .collect()
+}
+
+pub(crate) fn letter_index(letter: char) -> usize {
+    (letter.to_ascii_lowercase() as u8 - b'a') as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn euclidean_units() {
+        let model = Euclidean::default();
+        assert_eq!(1, model.quantized_distance(&QWERTY, 'o', 'p'));
+        assert_eq!(9, model.quantized_distance(&QWERTY, 'p', 'q'));
+
+        let model = Euclidean { unit: 25.0 };
+        assert_eq!(2, model.quantized_distance(&QWERTY, 'o', 'p'));
+        assert_eq!(18, model.quantized_distance(&QWERTY, 'p', 'q'));
+    }
+
+    #[test]
+    fn manhattan_distance() {
+        let model = Manhattan::default();
+        assert_eq!(1, model.quantized_distance(&QWERTY, 'o', 'p'));
+
+        // 'q' to 'a' is 23 px across and 64 px down
+        assert_eq!(2, model.quantized_distance(&QWERTY, 'q', 'a'));
+        assert_eq!(1, Euclidean::default().quantized_distance(&QWERTY, 'q', 'a'));
+    }
+
+    #[test]
+    fn key_hops() {
+        let model = KeyHops::default();
+        assert_eq!(0, model.quantized_distance(&QWERTY, 'g', 'g'));
+        assert_eq!(1, model.quantized_distance(&QWERTY, 'g', 'h'));
+        assert_eq!(1, model.quantized_distance(&QWERTY, 'g', 't'));
+        assert_eq!(1, model.quantized_distance(&QWERTY, 'a', 'z'));
+        assert_eq!(9, model.quantized_distance(&QWERTY, 'q', 'p'));
+        assert_eq!(model.quantized_distance(&QWERTY, 'z', 'p'), model.quantized_distance(&QWERTY, 'p', 'z'));
+    }
+
+    #[test]
+    fn buckets_match_euclidean() {
+        let limits = (0..9).map(|i| 25.0 + 50.0 * i as f64).collect::<Vec<_>>();
+        let buckets = Buckets { limits };
+        let euclidean = Euclidean::default();
+
+        for l in 'a'..='z' {
+            for r in 'a'..='z' {
+                assert_eq!(euclidean.quantized_distance(&QWERTY, l, r), buckets.quantized_distance(&QWERTY, l, r));
+            }
+        }
+    }
+
+    #[test]
+    fn recorded_scores() {
+        let recorded = parse_recorded_scores("# guess answer score\npapal paint 00265\n\nAPPLE vivid 42521\npapal pause 00000\n").unwrap();
+        assert_eq!(3, recorded.len());
+        assert_eq!(RecordedScore { guess: "apple".to_string(), answer: "vivid".to_string(), score: "42521".parse().unwrap() }, recorded[1]);
+
+        let mismatches = find_mismatches(Scorer::qwertle(), &recorded);
+        assert_eq!(1, mismatches.len());
+        assert_eq!("pause", mismatches[0].0.answer);
+        assert_eq!("00317", mismatches[0].1.to_string());
+
+        assert!(parse_recorded_scores("papal paint").is_err());
+        assert!(parse_recorded_scores("papal paint 0x265").is_err());
+        assert!(parse_recorded_scores("papal paints 00265").is_err());
+
+        let error = parse_recorded_scores("papal paint 00265\npap4l paint 00265").unwrap_err();
+        assert_eq!("Line 2: 'pap4l' contains '4'; words can only use the letters a-z.", error.to_string());
+    }
+
+    #[test]
+    fn scorer_table() {
+        let scorer = Scorer::qwertle();
+        let model = Euclidean::default();
+
+        for (i, left) in ('a'..='z').enumerate() {
+            for (j, right) in ('a'..='z').enumerate() {
+                assert_eq!(model.quantized_distance(&QWERTY, left, right), scorer.table()[i][j]);
+            }
+        }
+
+        let hops = Scorer::new(QWERTY, Arc::new(KeyHops::default())).unwrap();
+        assert_eq!(9, hops.letter_distance('q', 'P'));
+
+        // Digits may go past 9
+        let fine = Scorer::new(QWERTY, parse_model("euclidean:20").unwrap()).unwrap();
+        assert_eq!(23, fine.letter_distance('q', 'p'));
+        assert_eq!(Score::new(&[23, 0]).unwrap(), fine.score_words("qa", "pa"));
+
+        // Models which put different letters 0 apart can't tell a solved score from a near miss
+        for model in ["buckets:60,1000", "euclidean:120", "manhattan:200"] {
+            let error = Scorer::new(QWERTY, parse_model(model).unwrap()).unwrap_err();
+            assert!(error.to_string().contains("0 apart"), "{model}: {error}");
+        }
+    }
+
+    #[test]
+    fn parse_models() {
+        assert!(parse_model("euclidean").is_ok());
+        assert!(parse_model("Manhattan:64").is_ok());
+        assert!(parse_model("hops:70").is_ok());
+        assert!(parse_model("buckets:20,60,120").is_ok());
+
+        assert!(parse_model("buckets").is_err());
+        assert!(parse_model("buckets:60,20").is_err());
+        assert!(parse_model("euclidean:-5").is_err());
+        assert!(parse_model("chebyshev").is_err());
+
+        let model = parse_model("buckets:20,60,120").unwrap();
+        assert_eq!(3, model.quantized_distance(&QWERTY, 'p', 'q'));
+    }
+}
@@ -0,0 +1,112 @@
+use std::fmt;
+use crate::score::MAX_WORD_LENGTH;
+
+// Everything which can be wrong with input to the library: words, scores, and the options and files describing them.
+#[derive(Debug, Clone, PartialEq)]
+pub enum QwertleError {
+    // A word contains something other than the letters a-z.
+    InvalidLetter { word: String, letter: char },
+
+    // A word isn't the length it needs to be (ex: a guess and answer of different lengths).
+    WrongLength { word: String, expected: usize },
+
+    // A score couldn't be read, or doesn't fit the guess it's for.
+    MalformedScore { score: String, reason: String },
+
+    // A word isn't in the word list.
+    UnknownWord(String),
+
+    // An option, date, layout, or line in a file couldn't be understood.
+    InvalidInput(String),
+
+    // A file couldn't be read or written.
+    Io { path: String, message: String },
+}
+
+impl fmt::Display for QwertleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QwertleError::InvalidLetter { word, letter } => write!(f, "'{word}' contains '{letter}'; words can only use the letters a-z."),
+            QwertleError::WrongLength { word, expected } => write!(f, "'{word}' has {} letters, but should have {expected}.", word.chars().count()),
+            QwertleError::MalformedScore { score, reason } => write!(f, "Score '{score}' {reason}"),
+            QwertleError::UnknownWord(word) => write!(f, "'{word}' is not in the word list."),
+            QwertleError::InvalidInput(message) => write!(f, "{message}"),
+            QwertleError::Io { path, message } => write!(f, "Unable to use '{path}': {message}"),
+        }
+    }
+}
+
+impl std::error::Error for QwertleError {}
+
+impl From<QwertleError> for String {
+    fn from(error: QwertleError) -> String {
+        error.to_string()
+    }
+}
+
+// Check a letter is a-z (in either case), returning it in lowercase.
+pub fn validate_letter(letter: char) -> Result<char, QwertleError> {
+    match letter.is_ascii_alphabetic() {
+        true => Ok(letter.to_ascii_lowercase()),
+        false => Err(QwertleError::InvalidLetter { word: letter.to_string(), letter }),
+    }
+}
+
+// Check a word is one to MAX_WORD_LENGTH letters a-z (in either case), returning it in lowercase.
+pub fn validate_word(word: &str) -> Result<String, QwertleError> {
+    if let Some(letter) = word.chars().find(|c| !c.is_ascii_alphabetic()) {
+        return Err(QwertleError::InvalidLetter { word: word.to_string(), letter });
+    }
+
+    if word.is_empty() || word.len() > MAX_WORD_LENGTH {
+        return Err(QwertleError::InvalidInput(format!("'{word}' must be one to {MAX_WORD_LENGTH} letters.")));
+    }
+
+    Ok(word.to_ascii_lowercase())
+}
+
+// Check a word is letters a-z and 'length' letters long.
+pub fn validate_word_length(word: &str, length: usize) -> Result<String, QwertleError> {
+    let word = validate_word(word)?;
+
+    match word.len() == length {
+        true => Ok(word),
+        false => Err(QwertleError::WrongLength { word, expected: length }),
+    }
+}
+
+// Check a score has a distance for each letter of a guess.
+pub fn validate_score(guess: &str, score: &crate::score::Score) -> Result<(), QwertleError> {
+    match score.len() == guess.len() {
+        true => Ok(()),
+        false => Err(QwertleError::MalformedScore { score: score.to_string(), reason: format!("has {} digits, but '{guess}' has {} letters.", score.len(), guess.len()) }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_words() {
+        assert_eq!(Ok("apple".to_string()), validate_word("APPle"));
+        assert_eq!(Err(QwertleError::InvalidLetter { word: "app1e".to_string(), letter: '1' }), validate_word("app1e"));
+        assert!(matches!(validate_word(""), Err(QwertleError::InvalidInput(_))));
+        assert!(validate_word("abcdefghijklmnopq").is_err());
+
+        assert_eq!(Ok('q'), validate_letter('Q'));
+        assert!(validate_letter('-').is_err());
+
+        assert_eq!(Err(QwertleError::WrongLength { word: "apples".to_string(), expected: 5 }), validate_word_length("apples", 5));
+        assert!(validate_score("apple", &"4252".parse().unwrap()).is_err());
+        assert!(validate_score("apple", &"42521".parse().unwrap()).is_ok());
+    }
+
+    #[test]
+    fn error_messages() {
+        assert_eq!("'ap le' contains ' '; words can only use the letters a-z.", validate_word("ap le").unwrap_err().to_string());
+        assert_eq!("'apples' has 6 letters, but should have 5.", validate_word_length("apples", 5).unwrap_err().to_string());
+        assert_eq!("'qwert' is not in the word list.", QwertleError::UnknownWord("qwert".to_string()).to_string());
+        assert_eq!("Score '4252' has 4 digits, but 'apple' has 5 letters.", String::from(validate_score("apple", &"4252".parse().unwrap()).unwrap_err()));
+    }
+}
@@ -0,0 +1,207 @@
+use std::str::FromStr;
+use crate::{distance::Scorer, error::QwertleError, priors::Priors, score::*};
+
+// Likelihood multiplier for each step an actual digit is outside an observed range.
+// Players often mix up adjacent gradient shades, so digits marked uncertain ('4?') fall off slowly.
+// Digits entered plainly ('4' or '3~5') are trusted more, but a misread is still possible.
+const UNCERTAIN_FACTOR: f64 = 0.5;
+const CERTAIN_FACTOR: f64 = 0.05;
+
+// One observed score digit: a single distance ('4'), a distance which may be off by one or two ('4?'),
+// or an inclusive range of distances the tile could be ('3~5').
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DigitObservation {
+    pub low: u8,
+    pub high: u8,
+    pub uncertain: bool,
+}
+
+impl DigitObservation {
+    // How likely this observation is if the actual tile distance was 'digit'.
+    pub fn likelihood(&self, digit: u8) -> f64 {
+        let steps_away = self.low.saturating_sub(digit).max(digit.saturating_sub(self.high));
+
+        let factor = if self.uncertain { UNCERTAIN_FACTOR } else { CERTAIN_FACTOR };
+        factor.powi(steps_away as i32)
+    }
+}
+
+// An observed score where each digit may be uncertain. Ex: "4?253~51" is 4 (unsure), 2, 5, 3 to 5, 1.
+// As with Score, distances may instead be split by separators, so they can go past 9 (ex: "4?.12.5.3~11.1").
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuzzyScore {
+    pub digits: Vec<DigitObservation>,
+}
+
+impl FuzzyScore {
+    // How likely this observation is if the actual score was 'score'.
+    // If fewer digits were observed than the score has, they are compared to the last score digits.
+    pub fn likelihood(&self, score: &Score) -> f64 {
+        let actual = score.last(self.digits.len());
+        self.digits.iter().zip(actual.digits().iter()).map(|(observed, digit)| observed.likelihood(*digit)).product()
+    }
+}
+
+impl FromStr for FuzzyScore {
+    type Err = QwertleError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim();
+        let malformed = |reason: String| QwertleError::MalformedScore { score: text.to_string(), reason };
+
+        let parts = if text.contains(is_separator) {
+            text.split(is_separator).filter(|part| !part.is_empty()).map(|part| part.to_string()).collect::<Vec<_>>()
+        } else {
+            // One digit per tile, each followed by '?' or a range end ('~5') if it has one
+            let chars = text.chars().collect::<Vec<_>>();
+            let mut parts = Vec::new();
+            let mut i = 0;
+
+            while i < chars.len() {
+                let length = match chars.get(i + 1) {
+                    Some('?') => 2,
+                    Some('~') => 3,
+                    _ => 1,
+                };
+
+                parts.push(chars[i..(i + length).min(chars.len())].iter().collect::<String>());
+                i += length;
+            }
+
+            parts
+        };
+
+        let digits = parts.iter().map(|part| parse_observation(part).map_err(malformed)).collect::<Result<Vec<_>, _>>()?;
+        if digits.is_empty() || digits.len() > MAX_WORD_LENGTH {
+            return Err(malformed(format!("must have between one and {MAX_WORD_LENGTH} digits.")));
+        }
+
+        Ok(FuzzyScore { digits })
+    }
+}
+
+// Parse one observed distance: '4', '4?', or '3~5'. Errors are the reason the score is malformed.
+fn parse_observation(part: &str) -> Result<DigitObservation, String> {
+    let distance = |text: &str| text.parse::<u8>().map_err(|_| format!("has '{part}', which is not a distance."));
+
+    if let Some(low) = part.strip_suffix('?') {
+        let low = distance(low)?;
+        return Ok(DigitObservation { low, high: low, uncertain: true });
+    }
+
+    match part.split_once('~') {
+        Some((_, "")) => Err(format!("has a range '{part}' with no end.")),
+        Some((low, high)) => {
+            let (low, high) = (distance(low)?, distance(high)?);
+            if high < low {
+                return Err(format!("has a range {low}~{high} which is backwards."));
+            }
+
+            Ok(DigitObservation { low, high, uncertain: false })
+        }
+        None => {
+            let low = distance(part)?;
+            Ok(DigitObservation { low, high: low, uncertain: false })
+        }
+    }
+}
+
+// Rank answers by how likely they are given every guess and uncertain score seen so far, starting from the priors.
+// Returns each answer with its probability (normalized to sum to one) and most likely first.
+pub fn rank_candidates<'a>(scorer: &Scorer, turns: &[(&str, FuzzyScore)], answers: &[&'a str], priors: &Priors) -> Vec<(f64, &'a str)> {
+    let mut result = Vec::new();
+
+    for answer in answers {
+        let likelihood = turns.iter().map(|(guess, score)| score.likelihood(&scorer.score_words(guess, answer))).product::<f64>();
+        result.push((priors.weight(answer) * likelihood, *answer));
+    }
+
+    let total = result.iter().map(|(likelihood, _)| likelihood).sum::<f64>();
+    if total > 0.0 {
+        for (likelihood, _) in result.iter_mut() {
+            *likelihood /= total;
+        }
+    }
+
+    result.sort_by(|a, b| b.0.total_cmp(&a.0).then_with(|| a.1.cmp(b.1)));
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answers::ANSWERS;
+    use super::*;
+
+    #[test]
+    fn parse_fuzzy_score() {
+        let score = "4?253~51".parse::<FuzzyScore>().unwrap();
+        assert_eq!(5, score.digits.len());
+        assert_eq!(DigitObservation { low: 4, high: 4, uncertain: true }, score.digits[0]);
+        assert_eq!(DigitObservation { low: 2, high: 2, uncertain: false }, score.digits[1]);
+        assert_eq!(DigitObservation { low: 3, high: 5, uncertain: false }, score.digits[3]);
+
+        let spaced = "4?, 2, 5, 3~5, 1".parse::<FuzzyScore>().unwrap();
+        assert_eq!(score, spaced);
+
+        // Separated distances may go past 9, with the same separators as Score
+        let large = "4?.12.5.9~11.1".parse::<FuzzyScore>().unwrap();
+        assert_eq!(DigitObservation { low: 12, high: 12, uncertain: false }, large.digits[1]);
+        assert_eq!(DigitObservation { low: 9, high: 11, uncertain: false }, large.digits[3]);
+        assert_eq!(large, "4? 12 5 9~11 1".parse().unwrap());
+        assert_eq!("0-0-2-6-5".parse::<Score>().unwrap().digits(), "0-0-2-6-5".parse::<FuzzyScore>().unwrap().digits.iter().map(|d| d.low).collect::<Vec<_>>());
+
+        assert!("".parse::<FuzzyScore>().is_err());
+        assert!("4x253".parse::<FuzzyScore>().is_err());
+        assert!("5~3".parse::<FuzzyScore>().is_err());
+        assert!("4~".parse::<FuzzyScore>().is_err());
+        assert!("4.300".parse::<FuzzyScore>().is_err());
+        assert!("12345678901234567".parse::<FuzzyScore>().is_err());
+        assert_eq!("Score '1.1~x' has '1~x', which is not a distance.", "1.1~x".parse::<FuzzyScore>().unwrap_err().to_string());
+    }
+
+    #[test]
+    fn digit_likelihood() {
+        let exact = DigitObservation { low: 4, high: 4, uncertain: false };
+        assert_eq!(1.0, exact.likelihood(4));
+        assert_eq!(CERTAIN_FACTOR, exact.likelihood(5));
+
+        let unsure = DigitObservation { low: 4, high: 4, uncertain: true };
+        assert_eq!(UNCERTAIN_FACTOR, unsure.likelihood(3));
+        assert_eq!(UNCERTAIN_FACTOR * UNCERTAIN_FACTOR, unsure.likelihood(6));
+
+        let range = DigitObservation { low: 3, high: 5, uncertain: false };
+        assert_eq!(1.0, range.likelihood(3));
+        assert_eq!(1.0, range.likelihood(5));
+        assert_eq!(CERTAIN_FACTOR, range.likelihood(2));
+    }
+
+    #[test]
+    fn rank_with_misread_digit() {
+        // 'vivid' scores 42521 for 'apple'; a misread '3' for the first digit should still rank it highly
+        let turns = [("apple", "3?2521".parse::<FuzzyScore>().unwrap())];
+        let ranked = rank_candidates(Scorer::qwertle(), &turns, ANSWERS, &Priors::uniform());
+
+        assert_eq!("rigid", ranked[0].1);
+        assert_eq!("vivid", ranked[1].1);
+        assert!(ranked[1].0 > 0.1);
+
+        let total = ranked.iter().map(|(p, _)| p).sum::<f64>();
+        assert!((total - 1.0).abs() < 1e-9);
+
+        // A second turn pins down the answer
+        let turns = [("apple", "3?2521".parse::<FuzzyScore>().unwrap()), ("vivid", "00000".parse::<FuzzyScore>().unwrap())];
+        let ranked = rank_candidates(Scorer::qwertle(), &turns, ANSWERS, &Priors::uniform());
+        assert_eq!("vivid", ranked[0].1);
+    }
+
+    #[test]
+    fn rank_with_priors() {
+        // 'rigid' fits '3?2521' exactly, but a common 'vivid' overtakes it
+        let turns = [("apple", "3?2521".parse::<FuzzyScore>().unwrap())];
+        let priors = Priors::parse("rigid 1\nvivid 10\n").unwrap();
+        let ranked = rank_candidates(Scorer::qwertle(), &turns, ANSWERS, &priors);
+
+        assert_eq!("vivid", ranked[0].1);
+        assert_eq!("rigid", ranked[1].1);
+    }
+}
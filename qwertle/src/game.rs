@@ -0,0 +1,371 @@
+use std::collections::HashSet;
+use crate::{adversary::EvilHost, date::Date, dictionary::Dictionary, distance::Scorer, error::*, score::Score};
+
+// Guesses allowed per game, as in Wordle.
+pub const MAX_GUESSES: usize = 6;
+
+// Daily puzzles are numbered by days since Wordle's launch day, so an ordered list of the real answers (see parse_schedule)
+// gives the real answer for each date. Without one, the synthetic schedule is used: a fixed shuffle of the answer list made
+// up by this crate, whose answers don't match the real game's.
+pub const SCHEDULE_START: Date = Date { year: 2021, month: 6, day: 19 };
+
+// The synthetic daily answers are the answer list shuffled with this seed ("QWERTLE" in ASCII), so the schedule is fixed but not alphabetical.
+const SCHEDULE_SEED: u64 = 0x0051_5745_5254_4c45;
+
+// A small, fast, seedable random number generator (SplitMix64). Not for cryptography.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // A number from 0 up to (not including) 'limit'.
+    pub fn below(&mut self, limit: usize) -> usize {
+        (self.next_u64() % limit as u64) as usize
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+// The puzzle number for a date (0 on SCHEDULE_START), or None for earlier dates.
+pub fn puzzle_number(date: Date) -> Option<usize> {
+    let days = date.days() - SCHEDULE_START.days();
+    (days >= 0).then_some(days as usize)
+}
+
+// The synthetic answer for a date: the shuffled answer list, one per day, repeating once every answer has been used.
+pub fn daily_answer<'a>(answers: &[&'a str], date: Date) -> Option<&'a str> {
+    let number = puzzle_number(date)?;
+    if answers.is_empty() {
+        return None;
+    }
+
+    let schedule = schedule(answers);
+    Some(schedule[number % schedule.len()])
+}
+
+// Parse a list of answers already used, one 'date,word' per line (ex: 2022-01-05,rigid), such as the real past answers
+// of the puzzle being played. Blank lines and lines starting with '#' are skipped.
+pub fn parse_past_answers(text: &str) -> Result<Vec<(Date, String)>, QwertleError> {
+    let mut past = Vec::new();
+
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let parts = line.split(|c: char| c == ',' || c.is_whitespace()).filter(|part| !part.is_empty()).collect::<Vec<_>>();
+        let entry = match parts[..] {
+            [date, word] => date.parse::<Date>().and_then(|date| Ok((date, validate_word(word)?))),
+            _ => Err(QwertleError::InvalidInput(format!("should be 'date,word', not '{line}'."))),
+        };
+
+        past.push(entry.map_err(|error| QwertleError::InvalidInput(format!("Line {}: {error}", number + 1)))?);
+    }
+
+    Ok(past)
+}
+
+// The answers not used before a date by the past answers, in their original order.
+pub fn unused_answers<'a>(answers: &[&'a str], past: &[(Date, String)], date: Date) -> Vec<&'a str> {
+    let used = past.iter().filter(|(day, _)| *day < date).map(|(_, word)| word.as_str()).collect::<HashSet<_>>();
+    answers.iter().copied().filter(|answer| !used.contains(answer)).collect()
+}
+
+// Read a daily schedule: the answers in the order they were used, one per line, so the Nth word is the answer for puzzle N
+// (counting from 0 on SCHEDULE_START). Blank lines and lines starting with '#' are skipped.
+pub fn parse_schedule(text: &str) -> Result<Vec<String>, QwertleError> {
+    let mut schedule = Vec::new();
+
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        schedule.push(validate_word(line).map_err(|error| QwertleError::InvalidInput(format!("Line {}: {error}", number + 1)))?);
+    }
+
+    if schedule.is_empty() {
+        return Err(QwertleError::InvalidInput("The schedule has no answers.".to_string()));
+    }
+
+    Ok(schedule)
+}
+
+// The answer a schedule has for a date, or None before SCHEDULE_START or after its last day.
+pub fn scheduled_answer(schedule: &[String], date: Date) -> Option<&str> {
+    schedule.get(puzzle_number(date)?).map(|answer| answer.as_str())
+}
+
+// The order the synthetic daily answers are used in.
+fn schedule<'a>(answers: &[&'a str]) -> Vec<&'a str> {
+    let mut schedule = answers.to_vec();
+    Rng::new(SCHEDULE_SEED).shuffle(&mut schedule);
+    schedule
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameStatus {
+    Playing,
+    Won,
+    Lost,
+}
+
+// One game hosted by the crate: a hidden answer, the guesses made so far, and their scores.
+pub struct Game<'a> {
+    dictionary: &'a Dictionary,
+    scorer: Scorer,
+    host: Host<'a>,
+    turns: Vec<(String, Score)>,
+}
+
+// Who chooses the answer: a word picked before the game, or an evil host which puts the choice off as long as it can.
+enum Host<'a> {
+    Fixed(String),
+    Evil(Box<EvilHost<'a>>),
+}
+
+impl<'a> Game<'a> {
+    pub fn new(dictionary: &'a Dictionary, scorer: &Scorer, answer: &str) -> Game<'a> {
+        Game { dictionary, scorer: scorer.clone(), host: Host::Fixed(answer.to_ascii_lowercase()), turns: Vec::new() }
+    }
+
+    // Play against an evil host (see EvilHost): the hardest game possible, since every score keeps the most answers possible.
+    pub fn evil(dictionary: &'a Dictionary, scorer: &Scorer) -> Result<Game<'a>, QwertleError> {
+        if dictionary.word_length().is_none() {
+            return Err(QwertleError::InvalidInput("There are no answers to choose from.".to_string()));
+        }
+
+        let host = Host::Evil(Box::new(EvilHost::new(scorer, &dictionary.answers())));
+        Ok(Game { dictionary, scorer: scorer.clone(), host, turns: Vec::new() })
+    }
+
+    // Play a random answer. The same seed always picks the same answer.
+    pub fn seeded(dictionary: &'a Dictionary, scorer: &Scorer, seed: u64) -> Result<Game<'a>, QwertleError> {
+        let answers = dictionary.answers();
+        if answers.is_empty() {
+            return Err(QwertleError::InvalidInput("There are no answers to choose from.".to_string()));
+        }
+
+        let answer = answers[Rng::new(seed).below(answers.len())];
+        Ok(Game::new(dictionary, scorer, answer))
+    }
+
+    // Play the synthetic schedule's answer for a date.
+    pub fn daily(dictionary: &'a Dictionary, scorer: &Scorer, date: Date) -> Result<Game<'a>, QwertleError> {
+        match daily_answer(&dictionary.answers(), date) {
+            Some(answer) => Ok(Game::new(dictionary, scorer, answer)),
+            None => Err(QwertleError::InvalidInput(format!("There is no puzzle for {date}; the schedule starts on {SCHEDULE_START}."))),
+        }
+    }
+
+    // Play the answer an ordered schedule (see parse_schedule) has for a date.
+    pub fn scheduled(dictionary: &'a Dictionary, scorer: &Scorer, schedule: &[String], date: Date) -> Result<Game<'a>, QwertleError> {
+        let Some(answer) = scheduled_answer(schedule, date) else {
+            let last = SCHEDULE_START.add_days(schedule.len() as i64 - 1);
+            return Err(QwertleError::InvalidInput(format!("The schedule has no puzzle for {date}; it runs from {SCHEDULE_START} to {last}.")));
+        };
+
+        // The answer must be guessable, or the game can't be won
+        if !dictionary.is_allowed_guess(answer) {
+            return Err(QwertleError::UnknownWord(answer.to_string()));
+        }
+
+        Ok(Game::new(dictionary, scorer, answer))
+    }
+
+    // Score a guess. Guesses must be allowed words of the answer's length, and the game must not be over.
+    pub fn guess(&mut self, word: &str) -> Result<Score, QwertleError> {
+        if self.status() != GameStatus::Playing {
+            return Err(QwertleError::InvalidInput("The game is over.".to_string()));
+        }
+
+        let word = validate_word_length(word.trim(), self.answer().len())?;
+        if !self.dictionary.is_allowed_guess(&word) {
+            return Err(QwertleError::UnknownWord(word));
+        }
+
+        let score = match &mut self.host {
+            Host::Fixed(answer) => self.scorer.score_words(&word, answer),
+            Host::Evil(host) => host.respond(&word),
+        };
+        self.turns.push((word, score));
+        Ok(score)
+    }
+
+    pub fn status(&self) -> GameStatus {
+        match self.turns.last() {
+            Some((_, score)) if score.is_solved() => GameStatus::Won,
+            _ if self.turns.len() >= MAX_GUESSES => GameStatus::Lost,
+            _ => GameStatus::Playing,
+        }
+    }
+
+    pub fn turns(&self) -> &[(String, Score)] {
+        &self.turns
+    }
+
+    pub fn guesses_left(&self) -> usize {
+        MAX_GUESSES.saturating_sub(self.turns.len())
+    }
+
+    // The answer, or for an evil host, the first answer it could still choose.
+    pub fn answer(&self) -> &str {
+        match &self.host {
+            Host::Fixed(answer) => answer,
+            Host::Evil(host) => host.candidates()[0],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{*, answers::ANSWERS};
+    use super::*;
+
+    #[test]
+    fn rng_is_repeatable() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        let values = (0..10).map(|_| a.next_u64()).collect::<Vec<_>>();
+        assert_eq!(values, (0..10).map(|_| b.next_u64()).collect::<Vec<_>>());
+
+        let mut rng = Rng::new(7);
+        assert!((0..1000).all(|_| rng.below(6) < 6));
+
+        let mut items = (0..50).collect::<Vec<_>>();
+        rng.shuffle(&mut items);
+        assert_ne!((0..50).collect::<Vec<_>>(), items);
+        items.sort();
+        assert_eq!((0..50).collect::<Vec<_>>(), items);
+    }
+
+    #[test]
+    fn daily_schedule() {
+        let dictionary = Dictionary::embedded();
+        let answers = dictionary.answers();
+
+        assert_eq!(Some(0), puzzle_number(SCHEDULE_START));
+        assert_eq!(None, puzzle_number(SCHEDULE_START.add_days(-1)));
+        assert_eq!(None, daily_answer(&answers, SCHEDULE_START.add_days(-1)));
+
+        // Every answer is used once before the schedule repeats
+        let mut used = (0..answers.len()).map(|day| daily_answer(&answers, SCHEDULE_START.add_days(day as i64)).unwrap()).collect::<Vec<_>>();
+        assert_eq!(daily_answer(&answers, SCHEDULE_START), daily_answer(&answers, SCHEDULE_START.add_days(answers.len() as i64)));
+        used.sort();
+        assert_eq!(answers, used);
+    }
+
+    #[test]
+    fn real_schedule() {
+        let dictionary = Dictionary::embedded();
+        let schedule = parse_schedule("# puzzle order\ncigar\nREBUT\n\nsissy\n").unwrap();
+        assert_eq!(vec!["cigar", "rebut", "sissy"], schedule);
+
+        // Puzzle N is the Nth answer, counting days from SCHEDULE_START
+        assert_eq!(Some("cigar"), scheduled_answer(&schedule, SCHEDULE_START));
+        assert_eq!(Some("sissy"), scheduled_answer(&schedule, SCHEDULE_START.add_days(2)));
+        assert_eq!(None, scheduled_answer(&schedule, SCHEDULE_START.add_days(3)));
+        assert_eq!(None, scheduled_answer(&schedule, SCHEDULE_START.add_days(-1)));
+
+        let game = Game::scheduled(&dictionary, Scorer::qwertle(), &schedule, SCHEDULE_START.add_days(1)).unwrap();
+        assert_eq!("rebut", game.answer());
+
+        let error = Game::scheduled(&dictionary, Scorer::qwertle(), &schedule, SCHEDULE_START.add_days(3)).err().unwrap();
+        assert_eq!("The schedule has no puzzle for 2021-06-22; it runs from 2021-06-19 to 2021-06-21.", error.to_string());
+
+        let unknown = parse_schedule("qwert").unwrap();
+        assert!(matches!(Game::scheduled(&dictionary, Scorer::qwertle(), &unknown, SCHEDULE_START), Err(QwertleError::UnknownWord(_))));
+
+        assert!(parse_schedule("# nothing\n").is_err());
+        assert!(parse_schedule("cigar\nreb-t\n").unwrap_err().to_string().starts_with("Line 2: "));
+    }
+
+    #[test]
+    fn exclude_past_answers() {
+        let text = "# date,word\n2022-01-05,RIGID\n\n2022-01-06, vivid\n2022-01-07 taunt\n";
+        let past = parse_past_answers(text).unwrap();
+        assert_eq!(3, past.len());
+        assert_eq!((Date::new(2022, 1, 5).unwrap(), "rigid".to_string()), past[0]);
+
+        // Only answers used before the date are left out
+        let unused = unused_answers(ANSWERS, &past, Date::new(2022, 1, 7).unwrap());
+        assert_eq!(ANSWERS.len() - 2, unused.len());
+        assert!(!unused.contains(&"rigid") && !unused.contains(&"vivid"));
+        assert!(unused.contains(&"taunt"));
+        assert_eq!(ANSWERS.len(), unused_answers(ANSWERS, &past, Date::new(2022, 1, 5).unwrap()).len());
+
+        for bad in ["2022-01-05", "2022-01-05,rigid,extra", "2022-13-05,rigid", "2022-01-05,r1gid"] {
+            let error = parse_past_answers(&format!("2022-01-04,papal\n{bad}")).err().unwrap();
+            assert!(error.to_string().starts_with("Line 2: "), "{bad}: {error}");
+        }
+    }
+
+    #[test]
+    fn play_a_game() {
+        let dictionary = Dictionary::embedded();
+        let mut game = Game::new(&dictionary, Scorer::qwertle(), "rigid");
+        assert_eq!(GameStatus::Playing, game.status());
+
+        assert_eq!(Err(QwertleError::UnknownWord("qwert".to_string())), game.guess("qwert"));
+        assert_eq!(Err(QwertleError::WrongLength { word: "app".to_string(), expected: 5 }), game.guess("app"));
+        assert!(matches!(game.guess("app1e"), Err(QwertleError::InvalidLetter { letter: '1', .. })));
+        assert_eq!(0, game.turns().len());
+
+        assert_eq!(word_distance("apple", "rigid").unwrap(), game.guess("APPLE").unwrap());
+        assert_eq!(5, game.guesses_left());
+
+        assert!(game.guess("rigid").unwrap().is_solved());
+        assert_eq!(GameStatus::Won, game.status());
+        assert!(game.guess("apple").is_err());
+    }
+
+    #[test]
+    fn lose_a_game() {
+        let dictionary = Dictionary::embedded();
+        let mut game = Game::seeded(&dictionary, Scorer::qwertle(), 1).unwrap();
+        assert_eq!(game.answer(), Game::seeded(&dictionary, Scorer::qwertle(), 1).unwrap().answer());
+
+        let wrong = if game.answer() == "apple" { "papal" } else { "apple" };
+        for _ in 0..MAX_GUESSES {
+            game.guess(wrong).unwrap();
+        }
+
+        assert_eq!(GameStatus::Lost, game.status());
+        assert_eq!(0, game.guesses_left());
+    }
+
+    #[test]
+    fn play_evil_host() {
+        let dictionary = Dictionary::embedded();
+        let mut game = Game::evil(&dictionary, Scorer::qwertle()).unwrap();
+
+        // The host keeps the largest group for each guess, so 'apple' leaves four answers
+        let score = game.guess("apple").unwrap();
+        assert_eq!(word_distance("apple", game.answer()).unwrap(), score);
+        assert_eq!(GameStatus::Playing, game.status());
+        assert!(game.guess("apples").is_err());
+
+        let empty = Dictionary::from_text("", "").unwrap();
+        assert!(Game::evil(&empty, Scorer::qwertle()).is_err());
+    }
+}
@@ -0,0 +1,100 @@
+use std::fmt;
+use crate::{distance::Scorer, score::Score};
+
+// In hard mode, every guess must be a word which could still be the answer: if it were the answer,
+// each earlier guess would have gotten the score it actually got.
+
+// An earlier turn a proposed guess doesn't agree with.
+//  turn:      Which turn (1 for the first guess).
+//  expected:  The score the earlier guess got.
+//  actual:    The score the earlier guess would have gotten if the proposed guess were the answer.
+//  positions: The letter positions (from 0) where the two scores differ.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Violation {
+    pub turn: usize,
+    pub guess: String,
+    pub expected: Score,
+    pub actual: Score,
+    pub positions: Vec<usize>,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let positions = self.positions.iter().map(|p| (p + 1).to_string()).collect::<Vec<_>>();
+        write!(f, "Turn {}: '{}' scored {}, but would score {} (letter {}).", self.turn, self.guess, self.expected, self.actual, positions.join(", "))
+    }
+}
+
+// List every earlier turn the proposed guess isn't consistent with. An empty list means the guess is allowed.
+pub fn hard_mode_violations(scorer: &Scorer, history: &[(String, Score)], proposed: &str) -> Vec<Violation> {
+    let proposed = proposed.to_ascii_lowercase();
+    let mut violations = Vec::new();
+
+    for (i, (guess, expected)) in history.iter().enumerate() {
+        let actual = scorer.score_words(guess, &proposed);
+        if actual == *expected {
+            continue;
+        }
+
+        // Words of a different length can't match at all; every position is reported
+        let positions = if actual.len() == expected.len() {
+            (0..actual.len()).filter(|p| actual.digits()[*p] != expected.digits()[*p]).collect()
+        } else {
+            (0..expected.len()).collect()
+        };
+
+        violations.push(Violation { turn: i + 1, guess: guess.clone(), expected: *expected, actual, positions });
+    }
+
+    violations
+}
+
+// Whether a guess is allowed in hard mode after the given turns.
+pub fn is_hard_mode_guess(scorer: &Scorer, history: &[(String, Score)], proposed: &str) -> bool {
+    history.iter().all(|(guess, score)| scorer.score_words(guess, proposed) == *score)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{*, answers::ANSWERS};
+    use super::*;
+
+    #[test]
+    fn consistent_guesses() {
+        let scorer = Scorer::qwertle();
+        let history = vec![("papal".to_string(), "50365".parse::<Score>().unwrap())];
+
+        for answer in ["gamut", "gaunt", "taunt", "vaunt"] {
+            assert!(is_hard_mode_guess(scorer, &history, answer));
+            assert!(hard_mode_violations(scorer, &history, answer).is_empty());
+        }
+
+        // Exactly the answers which are still possible are allowed
+        let allowed = ANSWERS.iter().filter(|answer| is_hard_mode_guess(scorer, &history, answer)).count();
+        assert_eq!(4, allowed);
+
+        // Any guess is allowed before the first turn
+        assert!(is_hard_mode_guess(scorer, &[], "zzzzz"));
+    }
+
+    #[test]
+    fn violations() {
+        let scorer = Scorer::qwertle();
+        let history = vec![
+            ("apple".to_string(), word_distance("apple", "rigid").unwrap()),
+            ("papal".to_string(), word_distance("papal", "rigid").unwrap()),
+        ];
+
+        // 'rigod' only differs in the fourth letter, so each turn is off there
+        let violations = hard_mode_violations(scorer, &history, "RIGOD");
+        assert_eq!(2, violations.len());
+        assert_eq!(1, violations[0].turn);
+        assert_eq!("apple", violations[0].guess);
+        assert_eq!(vec![3], violations[0].positions);
+        assert_eq!(word_distance("apple", "rigod").unwrap(), violations[0].actual);
+        assert!(violations[1].to_string().starts_with("Turn 2: 'papal' scored "));
+
+        let short = hard_mode_violations(scorer, &history, "rig");
+        assert_eq!(vec![0, 1, 2, 3, 4], short[0].positions);
+    }
+}
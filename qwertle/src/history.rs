@@ -0,0 +1,266 @@
+use std::{fs::{self, OpenOptions}, io::Write, path::Path};
+use serde::{Deserialize, Serialize};
+use crate::{date::Date, error::*, game::*, matrix::ScoreMatrix, recommend::RankBy, score::Score, solver::Solver};
+
+// Where games are recorded unless --history gives another file.
+pub const DEFAULT_HISTORY_PATH: &str = "qwertle-history.jsonl";
+
+// One finished game: the answer, and each guess with its score. Daily games also have their date.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameRecord {
+    pub date: Option<Date>,
+    pub answer: String,
+    pub turns: Vec<(String, Score)>,
+}
+
+// Format for the history file: one game per line, as JSON.
+//  { "date": "2026-10-18", "answer": "rigid", "turns": [["papal", "67576"], ["rigid", "00000"]] }
+#[derive(Serialize, Deserialize)]
+struct RecordLine {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    date: Option<String>,
+    answer: String,
+    turns: Vec<(String, String)>,
+}
+
+impl GameRecord {
+    // Record a game played with 'play'. Only daily games have a date.
+    pub fn from_game(game: &Game, date: Option<Date>) -> GameRecord {
+        GameRecord { date, answer: game.answer().to_string(), turns: game.turns().to_vec() }
+    }
+
+    pub fn won(&self) -> bool {
+        self.turns.last().is_some_and(|(_, score)| score.is_solved())
+    }
+
+    // Check every guess is a word of the answer's length, with a score for each letter.
+    pub fn validate(&self) -> Result<(), QwertleError> {
+        validate_word(&self.answer)?;
+        for (guess, score) in self.turns.iter() {
+            validate_word_length(guess, self.answer.len())?;
+            validate_score(guess, score)?;
+        }
+
+        Ok(())
+    }
+
+    // Write the record as one line of JSON (see RecordLine), without the line break.
+    pub fn to_line(&self) -> String {
+        let line = RecordLine {
+            date: self.date.map(|date| date.to_string()),
+            answer: self.answer.clone(),
+            turns: self.turns.iter().map(|(guess, score)| (guess.clone(), score.to_string())).collect(),
+        };
+
+        serde_json::to_string(&line).unwrap()
+    }
+
+    pub fn parse_line(text: &str) -> Result<GameRecord, QwertleError> {
+        let line: RecordLine = serde_json::from_str(text).map_err(|e| QwertleError::InvalidInput(format!("Invalid game JSON: {e}")))?;
+
+        let record = GameRecord {
+            date: line.date.map(|date| date.parse()).transpose()?,
+            answer: line.answer.to_ascii_lowercase(),
+            turns: line.turns.iter().map(|(guess, score)| Ok((guess.to_ascii_lowercase(), score.parse()?))).collect::<Result<_, QwertleError>>()?,
+        };
+
+        record.validate()?;
+        Ok(record)
+    }
+}
+
+// Read every game in a history file, skipping blank lines. Errors give the line number.
+pub fn parse_history(text: &str) -> Result<Vec<GameRecord>, QwertleError> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(number, line)| GameRecord::parse_line(line).map_err(|error| QwertleError::InvalidInput(format!("Line {}: {error}", number + 1))))
+        .collect()
+}
+
+pub fn load_history(path: &Path) -> Result<Vec<GameRecord>, QwertleError> {
+    let text = fs::read_to_string(path).map_err(|e| QwertleError::Io { path: path.display().to_string(), message: e.to_string() })?;
+    parse_history(&text)
+}
+
+// Add a game to the end of a history file, creating the file if needed.
+pub fn append_record(path: &Path, record: &GameRecord) -> Result<(), QwertleError> {
+    let io_error = |e: std::io::Error| QwertleError::Io { path: path.display().to_string(), message: e.to_string() };
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path).map_err(io_error)?;
+    writeln!(file, "{}", record.to_line()).map_err(io_error)
+}
+
+// One turn of a replayed game, next to the guess the solver would have made instead.
+//  candidates_before:  Answers still possible before the guess.
+//  candidates_after:   Answers still possible after its score.
+//  recommended:        The solver's guess for this turn.
+//  recommended_after:  Answers which would have been left after the recommended guess's score against the answer.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TurnReview<'a> {
+    pub guess: String,
+    pub score: Score,
+    pub candidates_before: usize,
+    pub candidates_after: usize,
+    pub recommended: Option<&'a str>,
+    pub recommended_after: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GameReview<'a> {
+    pub date: Option<Date>,
+    pub answer: String,
+    pub won: bool,
+    pub turns: Vec<TurnReview<'a>>,
+}
+
+// Replay a game through the solver, turn by turn. Each recorded score must be the score the matrix's keyboard
+// and distance model give, or the answer would be ruled out; play with the same --layout and --metric.
+pub fn review_game<'a>(matrix: &'a ScoreMatrix<'a>, record: &GameRecord, rank_by: RankBy) -> Result<GameReview<'a>, QwertleError> {
+    let scorer = matrix.scorer();
+    let mut solver = Solver::new(matrix);
+    solver.set_rank_by(rank_by);
+
+    let mut turns = Vec::new();
+    for (guess, score) in record.turns.iter() {
+        let expected = scorer.score_words(guess, &record.answer);
+        if expected != *score {
+            return Err(QwertleError::InvalidInput(format!("'{guess}' was recorded as {score} against '{}', but the keyboard and metric give {expected}.", record.answer)));
+        }
+
+        let candidates = solver.candidates();
+        let recommended = solver.recommend();
+        let recommended_after = recommended.map(|word| {
+            let score = scorer.score_words(word, &record.answer);
+            scorer.score_map(word, &candidates).get(&score).map_or(0, |group| group.len())
+        });
+
+        solver.add_turn(guess, *score)?;
+        turns.push(TurnReview {
+            guess: guess.clone(),
+            score: *score,
+            candidates_before: candidates.len(),
+            candidates_after: solver.candidates().len(),
+            recommended,
+            recommended_after,
+        });
+    }
+
+    Ok(GameReview { date: record.date, answer: record.answer.clone(), won: record.won(), turns })
+}
+
+// Totals across reviewed games.
+//  histogram:     How many games were won in one guess, two guesses, and so on.
+//  behind_turns:  Turns which left more answers than the recommended guess would have.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HistorySummary {
+    pub games: usize,
+    pub won: usize,
+    pub average_guesses: f64,
+    pub histogram: Vec<usize>,
+    pub behind_turns: usize,
+}
+
+pub fn summarize(reviews: &[GameReview]) -> HistorySummary {
+    let wins = reviews.iter().filter(|review| review.won).collect::<Vec<_>>();
+
+    let mut histogram = vec![0; MAX_GUESSES];
+    for review in wins.iter() {
+        let guesses = review.turns.len();
+        if guesses > histogram.len() {
+            histogram.resize(guesses, 0);
+        }
+
+        histogram[guesses - 1] += 1;
+    }
+
+    let total_guesses = wins.iter().map(|review| review.turns.len()).sum::<usize>();
+    let behind_turns = reviews.iter()
+        .flat_map(|review| review.turns.iter())
+        .filter(|turn| turn.recommended_after.is_some_and(|after| turn.candidates_after > after))
+        .count();
+
+    HistorySummary {
+        games: reviews.len(),
+        won: wins.len(),
+        average_guesses: if wins.is_empty() { 0.0 } else { total_guesses as f64 / wins.len() as f64 },
+        histogram,
+        behind_turns,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{*, answers::ANSWERS, dictionary::Dictionary, distance::Scorer, recommend::rank_guesses};
+    use super::*;
+
+    fn record(answer: &str, guesses: &[&str]) -> GameRecord {
+        let turns = guesses.iter().map(|guess| (guess.to_string(), word_distance(guess, answer).unwrap())).collect();
+        GameRecord { date: Some(Date::new(2026, 10, 18).unwrap()), answer: answer.to_string(), turns }
+    }
+
+    #[test]
+    fn history_round_trip() {
+        let game = record("rigid", &["papal", "rigid"]);
+        let line = game.to_line();
+        assert_eq!(r#"{"date":"2026-10-18","answer":"rigid","turns":[["papal","67576"],["rigid","00000"]]}"#, line);
+        assert_eq!(game, GameRecord::parse_line(&line).unwrap());
+        assert!(game.won());
+
+        let undated = r#"{"answer":"RIGID","turns":[["papal","67576"]]}"#;
+        let parsed = parse_history(&format!("{line}\n\n{undated}\n")).unwrap();
+        assert_eq!(2, parsed.len());
+        assert_eq!(None, parsed[1].date);
+        assert!(!parsed[1].won());
+
+        let error = parse_history(&format!("{line}\n{{\"answer\":\"rigid\",\"turns\":[[\"papal\",\"6757\"]]}}")).err().unwrap();
+        assert!(error.to_string().starts_with("Line 2: "));
+        assert!(parse_history("not json").is_err());
+    }
+
+    #[test]
+    fn append_and_load() {
+        let path = std::env::temp_dir().join(format!("qwertle-history-test-{}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let dictionary = Dictionary::embedded();
+        let mut game = Game::new(&dictionary, Scorer::qwertle(), "vivid");
+        game.guess("papal").unwrap();
+        game.guess("vivid").unwrap();
+
+        append_record(&path, &GameRecord::from_game(&game, None)).unwrap();
+        append_record(&path, &record("rigid", &["papal", "rigid"])).unwrap();
+
+        let history = load_history(&path).unwrap();
+        assert_eq!(vec!["vivid", "rigid"], history.iter().map(|game| game.answer.as_str()).collect::<Vec<_>>());
+        assert_eq!(game.turns(), history[0].turns.as_slice());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn review_against_solver() {
+        let matrix = ScoreMatrix::build(Scorer::qwertle(), ANSWERS, ANSWERS).unwrap();
+
+        let review = review_game(&matrix, &record("taunt", &["apple", "gaunt", "taunt"]), RankBy::Entropy).unwrap();
+        assert!(review.won);
+        assert_eq!(ANSWERS.len(), review.turns[0].candidates_before);
+        assert_eq!(review.turns[0].candidates_after, review.turns[1].candidates_before);
+        assert_eq!(1, review.turns[2].candidates_after);
+
+        // The first recommendation is the best ranked opener
+        let recommended = rank_guesses(&matrix, &(0..ANSWERS.len()).collect::<Vec<_>>(), RankBy::Entropy)[0].guess;
+        assert_eq!(Some(recommended), review.turns[0].recommended);
+        assert!(review.turns[0].recommended_after.is_some());
+
+        let summary = summarize(&[review.clone(), review_game(&matrix, &record("taunt", &["apple"]), RankBy::Entropy).unwrap()]);
+        assert_eq!(2, summary.games);
+        assert_eq!(1, summary.won);
+        assert_eq!(3.0, summary.average_guesses);
+        assert_eq!(vec![0, 0, 1, 0, 0, 0], summary.histogram);
+
+        // Scores from another keyboard don't match the answer
+        let mut wrong = record("taunt", &["apple"]);
+        wrong.turns[0].1 = "11111".parse().unwrap();
+        assert!(review_game(&matrix, &wrong, RankBy::Entropy).is_err());
+    }
+}
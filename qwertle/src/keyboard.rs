@@ -0,0 +1,192 @@
+use std::{borrow::Cow, collections::HashMap, fs, path::Path};
+use serde::Deserialize;
+use crate::{error::QwertleError, letter_distances::LETTER_POSITIONS};
+
+// The x position of each key slot in the top, middle, and bottom rows of the QWERTLE keyboard.
+// Rows are staggered, so slot 'i' is a bit further right on each lower row.
+const ROW_SLOTS: [&[u16]; 3] = [
+    &[8, 58, 108, 158, 208, 258, 308, 358, 408, 458],
+    &[31, 81, 132, 182, 232, 283, 333, 384, 434, 485],
+    &[81, 132, 182, 232, 283, 333, 384, 434, 485, 535],
+];
+
+const ROW_Y: [u16; 3] = [8, 72, 136];
+
+// Where each letter key is on a keyboard, used to compute the pixel distance between any two letters.
+// Positions are the top left corner of each key, indexed by letter ('a' = 0).
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyboardLayout {
+    pub name: Cow<'static, str>,
+    pub positions: [(u16, u16); 26],
+}
+
+// The layout used by QWERTLE itself.
+pub const QWERTY: KeyboardLayout = KeyboardLayout { name: Cow::Borrowed("qwerty"), positions: LETTER_POSITIONS };
+
+// Format for custom layout files: the position of every letter key.
+//  { "name": "my-layout", "keys": { "a": [31, 72], "b": [283, 136], ... } }
+#[derive(Deserialize)]
+struct LayoutFile {
+    name: Option<String>,
+    keys: HashMap<String, (u16, u16)>,
+}
+
+impl KeyboardLayout {
+    pub fn qwerty() -> KeyboardLayout {
+        QWERTY
+    }
+
+    pub fn dvorak() -> KeyboardLayout {
+        KeyboardLayout::from_rows("dvorak", ["',.pyfgcrl", "aoeuidhtns", ";qjkxbmwvz"]).unwrap()
+    }
+
+    pub fn colemak() -> KeyboardLayout {
+        KeyboardLayout::from_rows("colemak", ["qwfpgjluy;", "arstdhneio", "zxcvbkm,./"]).unwrap()
+    }
+
+    pub fn azerty() -> KeyboardLayout {
+        KeyboardLayout::from_rows("azerty", ["azertyuiop", "qsdfghjklm", "wxcvbn,;:!"]).unwrap()
+    }
+
+    pub fn built_in_names() -> &'static [&'static str] {
+        &["qwerty", "dvorak", "colemak", "azerty"]
+    }
+
+    // Find a built-in layout by name.
+    pub fn by_name(name: &str) -> Option<KeyboardLayout> {
+        match name.to_ascii_lowercase().as_str() {
+            "qwerty" => Some(KeyboardLayout::qwerty()),
+            "dvorak" => Some(KeyboardLayout::dvorak()),
+            "colemak" => Some(KeyboardLayout::colemak()),
+            "azerty" => Some(KeyboardLayout::azerty()),
+            _ => None,
+        }
+    }
+
+    // Build a layout from the characters on each row, placed on the same key slots as the QWERTLE keyboard.
+    // Non-letters are placeholders for keys which aren't used in words. Every letter must appear exactly once.
+    pub fn from_rows(name: &str, rows: [&str; 3]) -> Result<KeyboardLayout, QwertleError> {
+        let mut keys = HashMap::new();
+
+        for (row, text) in rows.iter().enumerate() {
+            if text.chars().count() > ROW_SLOTS[row].len() {
+                return Err(QwertleError::InvalidInput(format!("Row {} of layout '{name}' has more than {} keys.", row + 1, ROW_SLOTS[row].len())));
+            }
+
+            for (slot, c) in text.chars().enumerate() {
+                if c.is_ascii_alphabetic() && keys.insert(c.to_ascii_lowercase(), (ROW_SLOTS[row][slot], ROW_Y[row])).is_some() {
+                    return Err(QwertleError::InvalidInput(format!("Layout '{name}' has more than one '{c}' key.")));
+                }
+            }
+        }
+
+        KeyboardLayout::from_keys(name, &keys)
+    }
+
+    // Build a layout from the position of each letter key.
+    pub fn from_keys(name: &str, keys: &HashMap<char, (u16, u16)>) -> Result<KeyboardLayout, QwertleError> {
+        let mut positions = [(0, 0); 26];
+
+        for (i, letter) in ('a'..='z').enumerate() {
+            positions[i] = *keys.get(&letter).ok_or_else(|| QwertleError::InvalidInput(format!("Layout '{name}' has no '{letter}' key.")))?;
+        }
+
+        Ok(KeyboardLayout { name: Cow::Owned(name.to_string()), positions })
+    }
+
+    // Parse a layout from JSON (see LayoutFile for the format).
+    pub fn from_json(text: &str) -> Result<KeyboardLayout, QwertleError> {
+        let file: LayoutFile = serde_json::from_str(text).map_err(|e| QwertleError::InvalidInput(format!("Invalid layout JSON: {e}")))?;
+        let name = file.name.unwrap_or("custom".to_string());
+
+        let mut keys = HashMap::new();
+        for (key, position) in file.keys {
+            let mut chars = key.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) if c.is_ascii_alphabetic() => { keys.insert(c.to_ascii_lowercase(), position); }
+                _ => return Err(QwertleError::InvalidInput(format!("Layout '{name}' key '{key}' is not a single letter."))),
+            }
+        }
+
+        KeyboardLayout::from_keys(&name, &keys)
+    }
+
+    // Use a built-in layout if 'name' is one, otherwise load it from a JSON file.
+    pub fn load(name_or_path: &str) -> Result<KeyboardLayout, QwertleError> {
+        if let Some(layout) = KeyboardLayout::by_name(name_or_path) {
+            return Ok(layout);
+        }
+
+        let text = fs::read_to_string(Path::new(name_or_path)).map_err(|e| QwertleError::Io { path: name_or_path.to_string(), message: e.to_string() })?;
+        KeyboardLayout::from_json(&text)
+    }
+
+    // Return the pixel distance between any two letters on this keyboard.
+    pub fn distance_between_letters(&self, mut left: char, mut right: char) -> f64 {
+        left = left.to_ascii_lowercase();
+        right = right.to_ascii_lowercase();
+
+        let (x1, y1) = self.positions[(left as u8 - b'a') as usize];
+        let (x2, y2) = self.positions[(right as u8 - b'a') as usize];
+
+        let (dx, dy) = (x2 as f64 - x1 as f64, y2 as f64 - y1 as f64);
+        (dx * dx + dy * dy).sqrt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn qwerty_from_rows() {
+        let layout = KeyboardLayout::from_rows("qwerty", ["qwertyuiop", "asdfghjkl;", "zxcvbnm,./"]).unwrap();
+        assert_eq!(QWERTY, layout);
+    }
+
+    #[test]
+    fn built_in_layouts() {
+        for name in KeyboardLayout::built_in_names() {
+            let layout = KeyboardLayout::by_name(name).unwrap();
+            assert_eq!(*name, layout.name);
+        }
+
+        assert_eq!(None, KeyboardLayout::by_name("qwertz"));
+
+        // Dvorak home row is 'aoeuidhtns', so 'a' and 'o' are neighbors
+        let dvorak = KeyboardLayout::dvorak();
+        assert_eq!(50.0, dvorak.distance_between_letters('a', 'o').round());
+        assert_eq!(QWERTY.positions[(b'a' - b'a') as usize], dvorak.positions[0]);
+
+        // AZERTY swaps 'a' and 'q'
+        let azerty = KeyboardLayout::azerty();
+        assert_eq!(QWERTY.distance_between_letters('q', 'w'), azerty.distance_between_letters('a', 'z'));
+    }
+
+    #[test]
+    fn bad_rows() {
+        assert!(KeyboardLayout::from_rows("short", ["qwertyuiop", "asdfghjkl", "zxcvbn"]).is_err());
+        assert!(KeyboardLayout::from_rows("twice", ["qwertyuiop", "asdfghjkl", "zxcvbnmm"]).is_err());
+        assert!(KeyboardLayout::from_rows("long", ["qwertyuiopq", "asdfghjkl", "zxcvbnm"]).is_err());
+    }
+
+    #[test]
+    fn layout_from_json() {
+        let mut keys = String::new();
+        for (i, letter) in ('a'..='z').enumerate() {
+            let (x, y) = QWERTY.positions[i];
+            if !keys.is_empty() {
+                keys += ", ";
+            }
+            keys += &format!("\"{letter}\": [{x}, {y}]");
+        }
+
+        let layout = KeyboardLayout::from_json(&format!("{{ \"name\": \"copy\", \"keys\": {{ {keys} }} }}")).unwrap();
+        assert_eq!("copy", layout.name);
+        assert_eq!(QWERTY.positions, layout.positions);
+
+        assert!(KeyboardLayout::from_json("{ \"keys\": { \"a\": [1, 2] } }").is_err());
+        assert!(KeyboardLayout::from_json("{ \"keys\": { \"ab\": [1, 2] } }").is_err());
+        assert!(KeyboardLayout::from_json("not json").is_err());
+    }
+}
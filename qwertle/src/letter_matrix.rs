@@ -0,0 +1,144 @@
+use serde::Serialize;
+use crate::{distance::{letter_index, Scorer}, priors::Priors, score::Score};
+
+// One letter which could be in a position, and the chance it is, from the answers still possible.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LetterChance {
+    pub letter: char,
+    pub probability: f64,
+}
+
+// The letters each position could still be after every turn so far.
+// A letter is allowed in a position if it's at the scored distance from the guess letter there in every turn.
+// Each allowed letter's probability is the share of the remaining answers (weighted by the priors) with that letter there,
+// so letters allowed by the distances alone, but in no remaining answer, have a probability of zero.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LetterMatrix {
+    pub candidates: usize,
+
+    // For each position, the allowed letters, most likely first.
+    pub positions: Vec<Vec<LetterChance>>,
+}
+
+impl LetterMatrix {
+    pub fn new(scorer: &Scorer, turns: &[(String, Score)], answers: &[&str], priors: &Priors) -> LetterMatrix {
+        let length = turns.first().map(|(guess, _)| guess.len()).or(answers.first().map(|answer| answer.len())).unwrap_or(0);
+
+        // Intersect the letters at each turn's distance, position by position
+        let mut allowed = vec![[true; 26]; length];
+        for (guess, score) in turns {
+            for (position, (letter, distance)) in guess.chars().zip(score.digits().iter()).enumerate().take(length) {
+                for (other, is_allowed) in ('a'..='z').zip(allowed[position].iter_mut()) {
+                    *is_allowed &= scorer.letter_distance(letter, other) == *distance;
+                }
+            }
+        }
+
+        let remaining = answers.iter()
+            .copied()
+            .filter(|answer| answer.len() == length && turns.iter().all(|(guess, score)| scorer.score_words(guess, answer) == *score))
+            .collect::<Vec<_>>();
+
+        let mut chances = vec![[0.0; 26]; length];
+        for (answer, probability) in remaining.iter().zip(priors.probabilities(&remaining)) {
+            for (position, letter) in answer.chars().enumerate() {
+                chances[position][letter_index(letter)] += probability;
+            }
+        }
+
+        let positions = allowed.iter().zip(chances.iter())
+            .map(|(allowed, chances)| {
+                let mut letters = ('a'..='z').zip(allowed.iter().zip(chances.iter()))
+                    .filter(|(_, (is_allowed, _))| **is_allowed)
+                    .map(|(letter, (_, probability))| LetterChance { letter, probability: *probability })
+                    .collect::<Vec<_>>();
+
+                letters.sort_by(|a, b| b.probability.total_cmp(&a.probability).then_with(|| a.letter.cmp(&b.letter)));
+                letters
+            })
+            .collect();
+
+        LetterMatrix { candidates: remaining.len(), positions }
+    }
+
+    // Show the letters for each position, most likely first, with the chance of each.
+    // Letters in no remaining answer are listed last, without a chance.
+    pub fn to_table(&self) -> String {
+        let mut text = String::new();
+
+        text += "| Pos | Letters                                                   |\n";
+        text += "|-----|-----------------------------------------------------------|\n";
+
+        for (position, letters) in self.positions.iter().enumerate() {
+            let cells = letters.iter()
+                .map(|chance| if chance.probability > 0.0 { format!("{} {:.1}%", chance.letter, chance.probability * 100.0) } else { chance.letter.to_string() })
+                .collect::<Vec<_>>();
+
+            text += &format!("| {:<3} | {:<57} |\n", position + 1, cells.join(", "));
+        }
+
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{*, answers::ANSWERS};
+    use super::*;
+
+    fn turn(guess: &str, score: &str) -> (String, Score) {
+        (guess.to_string(), score.parse().unwrap())
+    }
+
+    fn letters(chances: &[LetterChance]) -> String {
+        chances.iter().map(|chance| chance.letter).collect()
+    }
+
+    #[test]
+    fn no_turns() {
+        let matrix = LetterMatrix::new(Scorer::qwertle(), &[], ANSWERS, &Priors::uniform());
+        assert_eq!(ANSWERS.len(), matrix.candidates);
+        assert_eq!(5, matrix.positions.len());
+
+        // Every letter is allowed, ordered by how often it starts an answer
+        assert_eq!(26, matrix.positions[0].len());
+        assert_eq!('s', matrix.positions[0][0].letter);
+        assert!((matrix.positions[0][0].probability - 366.0 / 2315.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn intersect_turns() {
+        // One turn allows the letters at each distance, as in letter_options
+        let turns = vec![turn("apple", "42521")];
+        let matrix = LetterMatrix::new(Scorer::qwertle(), &turns, ANSWERS, &Priors::uniform());
+        assert_eq!(1, matrix.candidates);
+        assert_eq!(LetterChance { letter: 'v', probability: 1.0 }, matrix.positions[0][0]);
+        assert_eq!("vgt", letters(&matrix.positions[0]));
+
+        // A second turn keeps only the letters at the right distance from both guesses
+        let turns = vec![turn("papal", "50365"), turn("taunt", "10000")];
+        let matrix = LetterMatrix::new(Scorer::qwertle(), &turns, ANSWERS, &Priors::uniform());
+        assert_eq!(1, matrix.candidates);
+        assert_eq!(LetterChance { letter: 'g', probability: 1.0 }, matrix.positions[0][0]);
+        assert!(matrix.positions[0].iter().all(|chance| distance_between_letters_quantized('p', chance.letter).unwrap() == 5 && distance_between_letters_quantized('t', chance.letter).unwrap() == 1));
+        assert_eq!("a", letters(&matrix.positions[1]));
+    }
+
+    #[test]
+    fn weighted_letters() {
+        let turns = vec![turn("papal", "50365")];
+        let priors = Priors::parse("gamut 1\ngaunt 1\ntaunt 6\nvaunt 2\n").unwrap();
+        let matrix = LetterMatrix::new(Scorer::qwertle(), &turns, ANSWERS, &priors);
+        assert_eq!(4, matrix.candidates);
+
+        // 't' starts 'taunt', the most likely answer; 'g' starts two less likely ones
+        let first = &matrix.positions[0];
+        assert_eq!(LetterChance { letter: 't', probability: 0.6 }, first[0]);
+        assert_eq!('g', first[1].letter);
+        assert!((first[1].probability - 0.2).abs() < 1e-9);
+        assert!(first[2..].iter().all(|chance| chance.probability < 0.21));
+
+        let table = matrix.to_table();
+        assert!(table.contains("| 1   | t 60.0%, g 20.0%, v 20.0%"));
+    }
+}
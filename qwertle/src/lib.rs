@@ -1,89 +1,65 @@
 use std::collections::HashMap;
-use letter_distances::*;
+use distance::*;
+use error::*;
+use keyboard::*;
+use score::*;
 
+pub mod adversary;
 pub mod answers;
+pub mod boards;
+pub mod colors;
+pub mod date;
+pub mod dictionary;
+pub mod distance;
+pub mod error;
+pub mod fuzzy;
+pub mod game;
+pub mod history;
+pub mod hard_mode;
+pub mod keyboard;
 pub mod letter_distances;
+pub mod letter_matrix;
+pub mod matrix;
+pub mod openers;
+pub mod output;
+pub mod priors;
+pub mod recommend;
+pub mod report;
+pub mod score;
+pub mod server;
+pub mod simulate;
+pub mod solver;
+pub mod tree;
+pub mod tui;
+pub mod wasm;
 
 // Return the pixel distance between any two letters on the QWERTLE keyboard.
-pub fn distance_between_letters(mut left: char, mut right: char) -> f64 {
-    left = left.to_ascii_lowercase();
-    right = right.to_ascii_lowercase();
-
-    let (x1, y1) = LETTER_POSITIONS[(left as u8 - b'a') as usize];
-    let (x2, y2) = LETTER_POSITIONS[(right as u8 - b'a') as usize];
-
-    let (dx, dy) = (x2 as f64 - x1 as f64, y2 as f64 - y1 as f64);
-    (dx * dx + dy * dy).sqrt()
+pub fn distance_between_letters(left: char, right: char) -> Result<f64, QwertleError> {
+    Ok(QWERTY.distance_between_letters(validate_letter(left)?, validate_letter(right)?))
 }
 
 // Return the quantized distance between any two letters on the QWERTLE keyboard.
-//  Each unit is 50 pixels, which is the distance away of one letter directly left or right.
-pub fn distance_between_letters_quantized(left: char, right: char) -> u8 {
-    let dist = distance_between_letters(left, right);
-    (dist / 50.0).round() as u8
-}
-
-// Compute the distance between each letter of two words.
-// Each distance is one digit in the returned number, with the last letter in the lowest (ones) digit.
-pub fn word_distance(left: &str, right: &str) -> u32 {
-    let mut distance = 0u32;
-
-    for (l, r) in left.chars().zip(right.chars()) {
-        distance *= 10;
-        distance += distance_between_letters_quantized(l, r) as u32;
-    }
-
-    distance
+pub fn distance_between_letters_quantized(left: char, right: char) -> Result<u8, QwertleError> {
+    Scorer::qwertle().distance_between_letters_quantized(left, right)
 }
 
-// Given a word_distance score, separate and return the distance digit per letter.
-pub fn score_to_digits(score: u32) -> Vec<u8> {
-    let mut digits = Vec::new();
-    let mut left = score;
-
-    for _ in 0..5 {
-        digits.push((left % 10) as u8);
-        left /= 10;
-    }
-
-    digits.reverse();
-    digits
-}
-
-// Compute the distance between two scores.
-// This is the sum of the absolute difference between each digit.
-pub fn score_distance(left: u32, right: u32) -> u32 {
-    let left_digits = score_to_digits(left);
-    let right_digits = score_to_digits(right);
-
-    let mut distance = 0u32;
-    for (l, r) in left_digits.iter().zip(right_digits.iter()) {
-        distance += ((*l as i32) - (*r as i32)).abs() as u32;
-    }
-
-    distance
+// Compute the distance between each letter of two words on the QWERTLE keyboard.
+pub fn word_distance(left: &str, right: &str) -> Result<Score, QwertleError> {
+    Scorer::qwertle().word_distance(left, right)
 }
 
 // Given a guess and answer set, build a map of each possible score and the answers which would have that score for the guess.
-pub fn word_distance_map<'a>(guess: &str, answers: &[&'a str]) -> HashMap<u32, Vec<&'a str>> {
-    let mut map: HashMap<u32, Vec<&str>> = HashMap::new();
-
-    for answer in answers {
-        let distance = word_distance(guess, answer);
-        let entry = map.entry(distance);
-        entry.or_insert(Vec::new()).push(*answer);
-    }
-
-    map
+pub fn word_distance_map<'a>(guess: &str, answers: &[&'a str]) -> Result<HashMap<Score, Vec<&'a str>>, QwertleError> {
+    Scorer::qwertle().word_distance_map(guess, answers)
 }
 
 // Given an answer map, compute the cluster vector of the map.
 // Entry C[i] in the vector is how many distinct groups of i+1 answers there are with the same score.
 // Cluster Vectors can be used to see how well a guess splits apart answers and the worst-case group sizes left.
-pub fn map_to_cv(map: &HashMap<u32, Vec<&str>>) -> Vec<u32> {
+pub fn map_to_cv(map: &HashMap<Score, Vec<&str>>) -> Vec<u32> {
     let mut cv = Vec::new();
 
-    for (_, answers) in map {
+    for answers in map.values() {
         let count = answers.len();
         while cv.len() < count {
             cv.push(0);
@@ -126,105 +102,185 @@ pub fn letter_frequencies(answers: &[&str]) -> HashMap<(char, u8), u16>{
     frequencies
 }
 
-// Given a guess and score (the distance colors), show the likely letters for each position.
-// Sort the letters so that the ones which appear most often in each position are listed first.
-pub fn letter_options(guess: &str, score: u32, frequencies: &HashMap<(char, u8), u16>) -> String {
-    let mut text = String::new();
-    let mut score_digits = score_to_digits(score);
+// Given a guess and score (the distance colors), show the likely letters for each position on the QWERTLE keyboard.
+pub fn letter_options(guess: &str, score: &Score, frequencies: &HashMap<(char, u8), u16>) -> Result<String, QwertleError> {
+    Scorer::qwertle().letter_options(guess, score, frequencies)
+}
 
-    // If fewer than five letters were passed, score them against the last score digits
-    while guess.len() < score_digits.len() {
-        score_digits.remove(0);
-    }
+// Show the letters at each distance from each guess letter on the QWERTLE keyboard, most likely first.
+pub fn letter_table(guess: &str, answers: &[&str]) -> Result<String, QwertleError> {
+    Scorer::qwertle().letter_table(guess, answers)
+}
+
+// Given a guess and score, show the answers which most closely match the score on the QWERTLE keyboard.
+pub fn answer_options<'a>(guess: &str, score: &Score, answers: &[&'a str], within: u32) -> Result<Vec<(u32, &'a str, Score)>, QwertleError> {
+    Scorer::qwertle().answer_options(guess, score, answers, within)
+}
 
-    for (letter, distance) in guess.chars().zip(score_digits.iter()) {
-        let letter = letter.to_ascii_uppercase();
-        text += &format!("{letter}{distance}\t");
+// Answers are scored letter by letter against the guess, so each must be letters a-z and the guess's length.
+fn validate_answers(guess: &str, answers: &[&str]) -> Result<(), QwertleError> {
+    for answer in answers {
+        validate_word_length(answer, guess.len())?;
     }
 
-    text += "\n";
+    Ok(())
+}
 
-    // Show letters that are at the expected distance from each guess letter, with the most likely letters first
-    for (pos, (letter, distance)) in guess.chars().zip(score_digits.iter()).enumerate() {
-        let options = letters_at_distance(letter, pos as u8, *distance, &frequencies);
-        for (_frequency, option) in options {
-            text += &format!("{option}");
-        }
+// The public Scorer functions check their input and return an error for letters outside a-z or words of different lengths.
+// Inside the crate, words are checked once up front and then scored millions of times (building a ScoreMatrix, simulating
+// games), so the crate uses the unchecked versions further down, which panic on anything else.
+impl Scorer {
+    // Return the quantized distance between any two letters, using this keyboard and distance model.
+    pub fn distance_between_letters_quantized(&self, left: char, right: char) -> Result<u8, QwertleError> {
+        Ok(self.letter_distance(validate_letter(left)?, validate_letter(right)?))
+    }
 
-        text += "\t";
+    // Compute the distance between each letter of two words of the same length.
+    pub fn word_distance(&self, left: &str, right: &str) -> Result<Score, QwertleError> {
+        let left = validate_word(left)?;
+        let right = validate_word_length(right, left.len())?;
+        Ok(self.score_words(&left, &right))
     }
 
-    text
-}
+    // Given a guess and answer set, build a map of each possible score and the answers which would have that score for the guess.
+    pub fn word_distance_map<'a>(&self, guess: &str, answers: &[&'a str]) -> Result<HashMap<Score, Vec<&'a str>>, QwertleError> {
+        let guess = validate_word(guess)?;
+        validate_answers(&guess, answers)?;
+        Ok(self.score_map(&guess, answers))
+    }
 
-pub fn letter_table(guess: &str, answers: &[&str]) -> String {
-    let mut text = String::new();
-    let frequencies = letter_frequencies(answers);
+    // Given a guess and score (the distance colors), show the likely letters for each position.
+    // Sort the letters so that the ones which appear most often in each position are listed first.
+    pub fn letter_options(&self, guess: &str, score: &Score, frequencies: &HashMap<(char, u8), u16>) -> Result<String, QwertleError> {
+        let guess = validate_word(guess)?;
+        let mut text = String::new();
 
-    text += "| 0     | 1     | 2     | 3     | 4     | 5     | 6     | 7     | 8     | 9     |\n";
-    text += "|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|\n";
+        // If fewer letters than score digits were passed, score them against the last score digits
+        let score = score.last(guess.len());
+        let score_digits = score.digits();
 
-    for (pos, letter) in guess.chars().enumerate() {
-        text += &format!("| {letter} ({pos}) |");
+        for (letter, distance) in guess.chars().zip(score_digits.iter()) {
+            let letter = letter.to_ascii_uppercase();
+            text += &format!("{letter}{distance}\t");
+        }
 
-        for distance in 1u8..=9 {
-            text += " ";
+        text += "\n";
 
-            let options = letters_at_distance(letter, pos as u8, distance, &frequencies);
-            for (_, other) in options.iter() {
-                text += &format!("{other}");
+        // Show letters that are at the expected distance from each guess letter, with the most likely letters first
+        for (pos, (letter, distance)) in guess.chars().zip(score_digits.iter()).enumerate() {
+            let options = self.letters_at_distance(letter, pos as u8, *distance, frequencies);
+            for (_frequency, option) in options {
+                text += &format!("{option}");
             }
 
-            for _ in (options.len())..=5 {
+            text += "\t";
+        }
+
+        Ok(text)
+    }
+
+    pub fn letter_table(&self, guess: &str, answers: &[&str]) -> Result<String, QwertleError> {
+        let guess = validate_word(guess)?;
+        let mut text = String::new();
+        let frequencies = letter_frequencies(answers);
+
+        // One column per distance up to the largest on the keyboard, which may be past 9 under some distance models
+        let max_distance = self.max_distance().max(1);
+        text += &(0..=max_distance).map(|distance| format!("| {distance:<6}")).collect::<String>();
+        text += "|\n";
+        text += &"|-------".repeat(max_distance as usize + 1);
+        text += "|\n";
+
+        for (pos, letter) in guess.chars().enumerate() {
+            text += &format!("| {letter} ({pos}) |");
+
+            for distance in 1..=max_distance {
                 text += " ";
+
+                let options = self.letters_at_distance(letter, pos as u8, distance, &frequencies);
+                for (_, other) in options.iter() {
+                    text += &format!("{other}");
+                }
+
+                for _ in (options.len())..=5 {
+                    text += " ";
+                }
+
+                text += "|";
             }
 
-            text += "|";
+            text += "\n";
         }
 
-        text += "\n";
+        Ok(text)
     }
 
-    text
+    // Given a guess and score, show the answers which most closely match the score,
+    //  in order by how closely they match the score.
+    pub fn answer_options<'a>(&self, guess: &str, score: &Score, answers: &[&'a str], within: u32) -> Result<Vec<(u32, &'a str, Score)>, QwertleError> {
+        let guess = validate_word(guess)?;
+        validate_answers(&guess, answers)?;
+        Ok(self.closest_answers(&guess, score, answers, within))
+    }
 
-}
+    // The unchecked versions, for letters a-z and words already checked to be the same length.
 
-// Find all letters at a given distance from a specific guess letter,
-//  and return in order of how commonly they occur at the specific word position.
-fn letters_at_distance(from_letter: char, at_position: u8, at_distance: u8, frequencies: &HashMap<(char, u8), u16>) -> Vec<(u16, char)> {
-    let mut options = Vec::new();
+    pub(crate) fn letter_distance(&self, left: char, right: char) -> u8 {
+        self.table()[letter_index(left)][letter_index(right)]
+    }
 
-    for option in 'a'..='z' {
-        let distance_round = distance_between_letters_quantized(from_letter, option);
-        if distance_round == at_distance {
-            let frequency = frequencies.get(&(option, at_position)).unwrap_or(&0);
-            options.push((*frequency, option));
-        }
+    // Words must be at most MAX_WORD_LENGTH letters (see Score::from_distances).
+    pub(crate) fn score_words(&self, left: &str, right: &str) -> Score {
+        Score::from_distances(left.chars().zip(right.chars()).map(|(l, r)| self.letter_distance(l, r)))
     }
 
-    options.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
-    options
-}
+    pub(crate) fn score_map<'a>(&self, guess: &str, answers: &[&'a str]) -> HashMap<Score, Vec<&'a str>> {
+        let mut map: HashMap<Score, Vec<&str>> = HashMap::new();
 
-// Given a guess and score, show the answers which most closely match the score,
-//  in order by how closely they match the score.
-pub fn answer_options<'a>(guess: &str, score: u32, answers: &[&'a str], within: u32) -> Vec<(u32, &'a str, u32)> {
-    let mut result = Vec::new();
+        for answer in answers {
+            let distance = self.score_words(guess, answer);
+            let entry = map.entry(distance);
+            entry.or_default().push(*answer);
+        }
 
-    for answer in answers {
-        let answer_score = word_distance(guess, *answer);
-        let distance = score_distance(score, answer_score);
+        map
+    }
 
-        if distance <= within {
-            result.push((distance, *answer, answer_score));
+    // Find all letters at a given distance from a specific guess letter,
+    //  and return in order of how commonly they occur at the specific word position.
+    pub(crate) fn letters_at_distance(&self, from_letter: char, at_position: u8, at_distance: u8, frequencies: &HashMap<(char, u8), u16>) -> Vec<(u16, char)> {
+        let mut options = Vec::new();
+
+        for option in 'a'..='z' {
+            let distance_round = self.letter_distance(from_letter, option);
+            if distance_round == at_distance {
+                let frequency = frequencies.get(&(option, at_position)).unwrap_or(&0);
+                options.push((*frequency, option));
+            }
         }
-    }
 
-    if within > 0 {
-        result.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
+        options.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+        options
     }
 
-    result
+    pub(crate) fn closest_answers<'a>(&self, guess: &str, score: &Score, answers: &[&'a str], within: u32) -> Vec<(u32, &'a str, Score)> {
+        let mut result = Vec::new();
+
+        for answer in answers {
+            let answer_score = self.score_words(guess, answer);
+            let distance = score.distance(&answer_score);
+
+            if distance <= within {
+                result.push((distance, *answer, answer_score));
+            }
+        }
+
+        if within > 0 {
+            result.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(b.1)));
+        }
+
+        result
+    }
 }
 
 #[cfg(test)]
@@ -235,49 +291,75 @@ mod tests {
     #[test]
     fn letter_distances() {
         for l in 'a'..='z' {
-            assert_eq!(0, distance_between_letters_quantized(l, l));
+            assert_eq!(0, distance_between_letters_quantized(l, l).unwrap());
         }
 
         for l in 'a'..='z' {
             for r in 'a'..='z' {
-                let left = distance_between_letters(l, r).round();
-                let right = distance_between_letters(r, l).round();
+                let left = distance_between_letters(l, r).unwrap().round();
+                let right = distance_between_letters(r, l).unwrap().round();
                 assert_eq!(left, right);
             }
         }
 
-        assert_eq!(1, distance_between_letters_quantized('O', 'p'));
-        assert_eq!(1, distance_between_letters_quantized('l', 'p'));
-        assert_eq!(2, distance_between_letters_quantized('K', 'P'));
-        assert_eq!(3, distance_between_letters_quantized('m', 'P'));
+        assert_eq!(1, distance_between_letters_quantized('O', 'p').unwrap());
+        assert_eq!(1, distance_between_letters_quantized('l', 'p').unwrap());
+        assert_eq!(2, distance_between_letters_quantized('K', 'P').unwrap());
+        assert_eq!(3, distance_between_letters_quantized('m', 'P').unwrap());
+
+        assert_eq!(4, distance_between_letters_quantized('g', 'a').unwrap());
+        assert_eq!(3, distance_between_letters_quantized('e', 'z').unwrap());
 
-        assert_eq!(4, distance_between_letters_quantized('g', 'a'));
-        assert_eq!(3, distance_between_letters_quantized('e', 'z'));
+        assert_eq!(382.0, distance_between_letters('p', 's').unwrap().round());
+        assert_eq!(398.0, distance_between_letters('p', 'z').unwrap().round());
+        assert_eq!(450.0, distance_between_letters('p', 'q').unwrap().round());
+    }
 
-        assert_eq!(382.0, distance_between_letters('p', 's').round());
-        assert_eq!(398.0, distance_between_letters('p', 'z').round());
-        assert_eq!(450.0, distance_between_letters('p', 'q').round());
+    fn score(text: &str) -> Score {
+        text.parse().unwrap()
     }
 
     #[test]
     fn word_distances() {
-        assert_eq!(0, word_distance("hello", "hello"));
-        assert_eq!(42521, word_distance("apple", "vivid"));
+        assert_eq!(score("00000"), word_distance("hello", "hello").unwrap());
+        assert_eq!(score("42521"), word_distance("apple", "vivid").unwrap());
+
+        // Words of other lengths get one distance per letter
+        assert_eq!(score("0000"), word_distance("word", "word").unwrap());
+        assert_eq!(score("0000001"), word_distance("letters", "letterz").unwrap());
+        assert_eq!(score("900000000002"), word_distance("qwertyuiopas", "pwertyuiopaq").unwrap());
     }
 
     #[test]
-    fn score_to_digits_test() {
-        assert_eq!(vec![0, 0, 1, 2, 3], score_to_digits(123));
-        assert_eq!(vec![0, 1, 0, 0, 0], score_to_digits(1000));
-        assert_eq!(vec![3, 5, 4, 6, 9], score_to_digits(35469));
+    fn invalid_input() {
+        assert_eq!(Err(QwertleError::InvalidLetter { word: "4".to_string(), letter: '4' }), distance_between_letters_quantized('4', 'a'));
+        assert!(distance_between_letters('a', ';').is_err());
+
+        assert_eq!(Err(QwertleError::InvalidLetter { word: "app-e".to_string(), letter: '-' }), word_distance("app-e", "vivid"));
+        assert_eq!(Err(QwertleError::WrongLength { word: "vivids".to_string(), expected: 5 }), word_distance("apple", "vivids"));
+        assert!(word_distance("", "").is_err());
+
+        assert!(word_distance_map("apple", &["vivid", "ri gid"]).is_err());
+        assert!(answer_options("apple", &score("42521"), &["vivid", "rigid!"], 0).is_err());
+
+        // Scorer methods check their words too, rather than panicking
+        assert_eq!(Err(QwertleError::InvalidLetter { word: "ap-le".to_string(), letter: '-' }), Scorer::qwertle().word_distance("ap-le", "vivid"));
+        assert!(Scorer::qwertle().distance_between_letters_quantized('a', '-').is_err());
+        assert!(Scorer::qwertle().letter_table("ap ple", ANSWERS).is_err());
+
+        // Answers of another length can't be scored against the guess
+        assert_eq!(Err(QwertleError::WrongLength { word: "vivids".to_string(), expected: 5 }), answer_options("apple", &score("42521"), &["vivids", "vivid"], 3));
+        assert_eq!(Err(QwertleError::WrongLength { word: "viv".to_string(), expected: 5 }), word_distance_map("apple", &["viv"]));
+        assert!(letter_options("ap ple", &score("42521"), &letter_frequencies(ANSWERS)).is_err());
+        assert!(letter_table("", ANSWERS).is_err());
     }
 
     #[test]
     fn map_and_cv() {
         assert_eq!(2315, ANSWERS.len());
 
-        let map = word_distance_map("apple", ANSWERS);
-        assert_eq!(2315, map.iter().map(|(_, v)| v.len()).sum::<usize>());
+        let map = word_distance_map("apple", ANSWERS).unwrap();
+        assert_eq!(2315, map.values().map(|v| v.len()).sum::<usize>());
 
         let cv = map_to_cv(&map);
         assert_eq!(vec![1912, 163, 23, 2], cv);
@@ -290,34 +372,26 @@ mod tests {
     fn letter_and_answer_options() {
         let frequencies = letter_frequencies(ANSWERS);
 
-        let options = letter_options("apple", 42521, &frequencies);
+        let options = letter_options("apple", &score("42521"), &frequencies).unwrap();
         assert_eq!("A4\tP2\tP5\tL2\tE1\t\ntgv\tik\ttgv\tnimj\trdsw\t", options);
 
         // Allow shorter values to be passed
-        let options = letter_options("a", 1, &frequencies);
+        let options = letter_options("a", &score("1"), &frequencies).unwrap();
         assert_eq!("A1\t\nswq\t", options);
 
-        let options = letter_options("aa", 12, &frequencies);
+        let options = letter_options("aa", &score("12"), &frequencies).unwrap();
         assert_eq!("A1\tA2\t\nswq\tedxz\t", options);
 
         // Look for whole word matches with different thresholds
-        let options = answer_options("apple", 42521, ANSWERS, 0);
-        assert_eq!(vec![(0, "vivid", 42521)], options);
+        let options = answer_options("apple", &score("42521"), ANSWERS, 0).unwrap();
+        assert_eq!(vec![(0, "vivid", score("42521"))], options);
 
-        let options = answer_options("apple", 42521, ANSWERS, 1);
-        assert_eq!(vec![(0, "vivid", 42521), (1, "rigid", 32521), (1, "vigor", 42511)], options);
-    }
-
-    #[test]
-    fn score_dist() {
-        assert_eq!(0, score_distance(52321, 52321));
+        let options = answer_options("apple", &score("42521"), ANSWERS, 1).unwrap();
+        assert_eq!(vec![(0, "vivid", score("42521")), (1, "rigid", score("32521")), (1, "vigor", score("42511"))], options);
 
-        assert_eq!(1, score_distance(51321, 52321));
-        assert_eq!(1, score_distance(53321, 52321));
-
-        assert_eq!(3, score_distance(51411, 52321));
-        assert_eq!(3, score_distance(82321, 52321));
-        assert_eq!(13, score_distance(0, 52321));
+        // Six letter words work the same way
+        let options = answer_options("papers", &word_distance("papers", "tapers").unwrap(), &["papers", "tapers", "wipers"], 0).unwrap();
+        assert_eq!(vec![(0, "tapers", score("500000"))], options);
     }
 
     #[test]
@@ -334,18 +408,32 @@ mod tests {
     fn letters_at_distance_tests() {
         let frequencies = letter_frequencies(ANSWERS);
 
-        let neighbors = letters_at_distance('a', 0, 1, &frequencies);
+        let neighbors = Scorer::qwertle().letters_at_distance('a', 0, 1, &frequencies);
         let letters = neighbors.iter().map(|(_, l)| l).collect::<String>();
         assert_eq!("swq", letters);
 
-        let neighbors = letters_at_distance('e', 4, 2, &frequencies);
+        let neighbors = Scorer::qwertle().letters_at_distance('e', 4, 2, &frequencies);
         let letters = neighbors.iter().map(|(_, l)| l).collect::<String>();
         assert_eq!("tafq", letters);
     }
 
+    #[test]
+    fn letter_table_large_distances() {
+        // With 20 px units, distances go up to 23 ('q' to 'p'), and every letter is in some column
+        let scorer = Scorer::new(QWERTY, distance::parse_model("euclidean:20").unwrap()).unwrap();
+        assert_eq!(23, scorer.max_distance());
+
+        let table = scorer.letter_table("q", ANSWERS).unwrap();
+        let lines = table.lines().collect::<Vec<_>>();
+        assert!(lines[0].ends_with("| 23    |"));
+        assert_eq!(lines[0].len(), lines[1].len());
+        assert!(lines[2].contains('p'));
+        assert_eq!(25, lines[2].chars().filter(|c| c.is_ascii_lowercase()).count() - 1);
+    }
+
     #[test]
     fn letter_table_test() {
-        let table = letter_table("apple", ANSWERS);
+        let table = letter_table("apple", ANSWERS).unwrap();
         let expected = "| 0     | 1     | 2     | 3     | 4     | 5     | 6     | 7     | 8     | 9     |
 |-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|
 | a (0) | swq   | dezx  | cfr   | tgv   | bhy   | nuj   | mik   | lo    | p     |
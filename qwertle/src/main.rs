@@ -1,20 +1,175 @@
-use std::{env, process::ExitCode};
-use qwertle::{answers::ANSWERS, *};
+use std::{env, fmt, fs, io::{self, BufRead, Write}, net::TcpListener, path::Path, process::ExitCode};
+use qwertle::{adversary::*, boards::MultiSolver, colors::*, date::Date, dictionary::Dictionary, distance::*, error::*, fuzzy::*, game::*, history::*, keyboard::KeyboardLayout, letter_matrix::LetterMatrix, matrix::ScoreMatrix, openers::*, output::*, priors::Priors, recommend::RankBy, report::*, score::Score, server::Server, simulate::*, solver::Solver, tree::DecisionTree, tui::*, *};
+use serde::Serialize;
 
 const USAGE: &str = "USAGE: 
-  qwertle <mode> <args>
+  qwertle <mode> <args> [--answers <path>] [--guesses <path>] [--layout <name|path>] [--metric <model>] [--cache <path>] [--format <format>]
+          [--frequencies <path>] [--unused <path> [--date <YYYY-MM-DD>]] [--colors <path>] [--history <path>]
+          [--schedule <path>]
 
 MODES:
-  best                              Find guess with the most distinct responses
+  best                              Find guess (from all allowed guesses) with the most distinct responses
+  report [--by <measure>] [--out <dir>]
+                                    Rate every allowed guess as an opener: distinct scores, entropy, expected and worst case answers left,
+                                    and each position's letter coverage (share of answers whose letter there the digit identifies).
+                                    Prints a Markdown summary, or writes every opener to <dir>/openers.csv and the summary to openers.md.
   word <guess>                      For a guess, response for each answer, cluster vector, and letter guess table.
   letter_options <guess> <score>    (lo) For a guess and score, show the possible letters at each position.
-  answer_options <guess> <score>    (ao) For a guess and score, show possible answers closest to the score.";
+  answer_options <guess> <score>    (ao) For a guess and score, show possible answers closest to the score.
+  letters <guess> <score>...        (lm) For every turn so far, show the letters still possible at each position and the chance of each.
+  fuzzy <guess> <score>...          (fz) Rank answers by likelihood across turns, allowing uncertain score digits.
+                                    Mark a digit '4?' if unsure, or give a range '3~5'. Ex: fuzzy papal 0?02~426
+                                    Split distances with dots or spaces, as in scores, to go past 9 (ex: 0?.12.2~11.4.6).
+  play [--date <YYYY-MM-DD> | --seed <n> | --evil]
+                                    Play a game: the daily answer for today (or --date), or a random answer picked with --seed.
+                                    The daily answer for a date is puzzle N, the number of days since 2021-06-19, in the --schedule
+                                    file, so a list of the real answers in order plays the real puzzle. Without --schedule, a synthetic
+                                    schedule is used: a fixed shuffle of the answers, which won't match the real game.
+                                    With --evil, the host never picks an answer: each score keeps as many answers possible as it can.
+                                    With --history, each finished game is added to the history file for stats.
+  record <answer> <guess> <score>...
+                                    Add a game played elsewhere to the --history file, dated today (or --date).
+  stats                             Replay every game in the --history file through the solver: the answers left after each guess,
+                                    next to the recommended guess and how many answers it would have left. Guesses are ranked with --by.
+  assist                            Interactive helper: for each guess, shows the keyboard colored by distance from each letter, asks
+                                    for each tile's distance, and narrows the answers as tiles are entered. Distances may be more than one
+                                    digit (ex: 11), and several tiles may be entered at once split by spaces or dots. Set NO_COLOR to
+                                    disable colors.
+  solve [--by <measure>] [--hard] [<guess> <score>]...
+                                    Narrow the answers across turns and recommend the next guess. Reads more turns from stdin.
+                                    Rank guesses by 'entropy' (default), 'expected' remaining answers, or 'worst' case (minimax).
+                                    With --hard, only guesses consistent with every earlier score are allowed and recommended.
+  boards <count> [--by <measure>] [<guess> <score>...]...
+                                    Solve several answers at once (2 for Dordle, 4 for Quordle): each guess is followed by its score on
+                                    every board not yet solved, in order. Recommends the guess with the most information across the boards.
+  openers [<words>] [--pool <n>]    Find fixed openers of two (default) or three words whose scores together best split the answers:
+                                    the smallest worst case, then the most distinct score combinations. Every word but the last comes
+                                    from the --pool (default 100) words with the smallest worst case alone. 'pairs' finds two word openers.
+  tree <opener>... [--out <path>]   Build a strategy tree from each opener and report the max and average guesses to solve every answer.
+                                    Later guesses are ranked with --by. Writes the tree as JSON to --out (one opener only).
+  minimax [<opener>]                Report the most guesses minimax play (always ranking by worst case) needs for any answer, which
+                                    no host can exceed, even with --evil. Opens with the guess with the smallest worst case by default.
+  decode <image.png | color>...     Read a score from the tile colors in a screenshot of one guess row, or from each color given
+                                    (hex like #87ff00, or 135,255,0). Prints the score, ready for answer_options or solve.
+  check <path>                      Check the --layout and --metric reproduce recorded scores ('guess answer score' per line).
+  simulate [<strategy>...]          Play every answer with each strategy and report guess counts, failures (over six guesses), and time.
+                                    Strategies: 'entropy' (default), 'expected', 'worst' (minimax), or 'first' (first possible answer),
+                                    with an optional fixed opener, which must be an allowed guess (ex: entropy:papal).
+  serve [<address:port>]            Answer JSON requests over HTTP (default 127.0.0.1:8080). Endpoints:
+                                      /distances?g=papal, /assess?g=papal&s=00265[&w=2], /letters?g=papal&s=00265, /table?g=papal
+
+OPTIONS:
+  --answers <path>                  Load possible answers from a file (one word per line) instead of the built-in Wordle answers.
+                                    Answers must all be the same length; repeated answers are counted once.
+  --guesses <path>                  Load extra allowed guesses from a file instead of the built-in Wordle guess list.
+  --layout <name|path>              Keyboard to measure letter distances on: 'qwerty' (default), 'dvorak', 'colemak', 'azerty',
+                                    or a JSON file of key positions: { \"name\": \"mine\", \"keys\": { \"a\": [31, 72], ... } }
+  --metric <model>                  How key distance becomes a digit: 'euclidean' (default), 'manhattan', or 'hops' (key-to-key steps),
+                                    with an optional ':<pixels>' unit, or 'buckets:<limit>,<limit>,...' with the pixel limit for each digit.
+                                    Distances may go past 9 (scores are then written with '.' between digits, ex: 0.12.3). Models which
+                                    put two different letters 0 apart are rejected, since a score of all zeros must mean solved.
+  --format <text|json|csv|md>       Write results as text (default), JSON, CSV, or a Markdown table (CSV and Markdown show the main table).
+  --cache <path>                    Save the guess by answer score matrix (used by best, report, solve, boards, stats, tree, and simulate) to a file,
+                                    and reuse it when the words, layout, and metric match.
+  --frequencies <path>              Weight answers by how common they are ('word count' per line), so letter_options, answer_options,
+                                    letters, fuzzy, and solve favor likely answers. Words not listed get the smallest count in the file.
+  --unused <path>                   Leave out answers used before today (or --date), listed in a file of past answers with one
+                                    'date,word' per line (ex: 2022-01-05,rigid). Lines starting with '#' are skipped.
+  --history <path>                  The game history file, one game per line as JSON (default qwertle-history.jsonl), for play, record,
+                                    and stats.
+  --schedule <path>                 The daily answers for play, in the order they were used, one per line (the first is 2021-06-19's).
+  --colors <path>                   Calibrate decode with the tile color for each distance ('digit color' per line, ex: 0 #6aaa64),
+                                    sampled from real tiles. The default is the green to red gradient used by assist and play.";
 
 fn main() -> ExitCode {
-    let args: Vec<String> = env::args().collect();
-    let mut args = &args[1..];
+    let mut args: Vec<String> = env::args().skip(1).collect();
+
+    let answers_path = take_option(&mut args, "--answers");
+    let guesses_path = take_option(&mut args, "--guesses");
+    let rank_by = take_option(&mut args, "--by");
+    let out_path = take_option(&mut args, "--out");
+    let layout = take_option(&mut args, "--layout");
+    let metric = take_option(&mut args, "--metric");
+    let cache_path = take_option(&mut args, "--cache");
+    let hard_mode = take_flag(&mut args, "--hard");
+    let format = take_option(&mut args, "--format");
+    let seed = take_option(&mut args, "--seed");
+    let date = take_option(&mut args, "--date");
+    let frequencies_path = take_option(&mut args, "--frequencies");
+    let unused_path = take_option(&mut args, "--unused");
+    let pool_size = take_option(&mut args, "--pool");
+    let colors_path = take_option(&mut args, "--colors");
+    let evil = take_flag(&mut args, "--evil");
+    let history_path = take_option(&mut args, "--history");
+    let schedule_path = take_option(&mut args, "--schedule");
+
+    let dictionary = match Dictionary::load(answers_path.as_deref().map(Path::new), guesses_path.as_deref().map(Path::new)) {
+        Ok(dictionary) => dictionary,
+        Err(error) => return print_error(&error),
+    };
+
+    let layout = match layout {
+        None => KeyboardLayout::qwerty(),
+        Some(name_or_path) => match KeyboardLayout::load(&name_or_path) {
+            Ok(layout) => layout,
+            Err(error) => return print_error(&error),
+        },
+    };
+
+    let model = match metric {
+        None => parse_model("euclidean"),
+        Some(text) => parse_model(&text),
+    };
+
+    let scorer = match model.and_then(|model| Scorer::new(layout, model)) {
+        Ok(scorer) => scorer,
+        Err(error) => return print_error(&error),
+    };
+
+    let rank_by = match rank_by {
+        None => RankBy::Entropy,
+        Some(name) => match RankBy::parse(&name) {
+            Some(rank_by) => rank_by,
+            None => return print_usage("--by must be 'entropy', 'expected', or 'worst' (minimax)."),
+        },
+    };
+
+    let format = match format {
+        None => Format::Text,
+        Some(name) => match Format::parse(&name) {
+            Some(format) => format,
+            None => return print_usage("--format must be 'text', 'json', 'csv', or 'md'."),
+        },
+    };
+
+    let date = match date {
+        None => Date::today(),
+        Some(text) => match text.parse::<Date>() {
+            Ok(date) => date,
+            Err(error) => return print_error(&error),
+        },
+    };
 
-    if args.len() < 1 {
+    let priors = match frequencies_path {
+        None => Priors::uniform(),
+        Some(path) => match read_file(&path).and_then(|text| Priors::parse(&text)) {
+            Ok(priors) => priors,
+            Err(error) => return print_error(&error),
+        },
+    };
+
+    let mut answers = dictionary.answers();
+    if let Some(path) = unused_path {
+        match read_file(&path).and_then(|text| parse_past_answers(&text)) {
+            Ok(past) => answers = unused_answers(&answers, &past, date),
+            Err(error) => return print_error(&error),
+        }
+    }
+
+    let guesses = dictionary.guesses();
+    let mut args = &args[..];
+
+    if args.is_empty() {
         return print_usage("No mode provided.");
     }
 
@@ -24,52 +179,98 @@ fn main() -> ExitCode {
 
     match mode {
         "best" => {
-            let mut best = None;
+            let matrix = match build_matrix(&scorer, &guesses, &answers, cache_path.as_deref()) {
+                Ok(matrix) => matrix,
+                Err(error) => return print_error(&error),
+            };
 
-            for guess in ANSWERS.iter() {
-                let map = word_distance_map(guess, ANSWERS);
-                let distinct_distances = map.len();
+            let counts = guess_distinct_counts(&matrix);
+            if print_formatted(&counts, format) {
+                return ExitCode::SUCCESS;
+            }
+
+            let mut best: Option<&GuessDistinct> = None;
 
-                if distinct_distances >= 2100 {
-                    println!("{distinct_distances}: {guess}");
+            for count in counts.iter() {
+                if count.distinct >= 2100 {
+                    println!("{}: {}", count.distinct, count.guess);
                 }
 
-                if distinct_distances > best.unwrap_or(("", 0)).1 {
-                    best = Some((&guess, distinct_distances));
+                if count.distinct > best.map_or(0, |b| b.distinct) {
+                    best = Some(count);
                 }
             }
 
-            if let Some((guess, distinct_distances)) = best {
-                println!("Best: {guess} ({distinct_distances} distinct distances).");
+            if let Some(best) = best {
+                println!("Best: {} ({} distinct distances).", best.guess, best.distinct);
             }
         }
 
+        "report" => {
+            const SUMMARY_LIMIT: usize = 25;
+
+            let matrix = match build_matrix(&scorer, &guesses, &answers, cache_path.as_deref()) {
+                Ok(matrix) => matrix,
+                Err(error) => return print_error(&error),
+            };
+
+            let stats = opener_stats(&matrix, rank_by);
+            if print_formatted(&stats, format) {
+                return ExitCode::SUCCESS;
+            }
+
+            let summary = summary_markdown(&stats, answers.len(), rank_by, SUMMARY_LIMIT);
+            let Some(dir) = out_path.as_ref() else {
+                print!("{summary}");
+                return ExitCode::SUCCESS;
+            };
+
+            let csv_path = Path::new(dir).join("openers.csv");
+            let summary_path = Path::new(dir).join("openers.md");
+            let written = fs::create_dir_all(dir)
+                .and_then(|_| fs::write(&csv_path, to_csv(&stats)))
+                .and_then(|_| fs::write(&summary_path, &summary));
+
+            if let Err(error) = written {
+                return print_error(&QwertleError::Io { path: dir.clone(), message: error.to_string() });
+            }
+
+            println!("Report on {} openers written to '{}' and '{}'.", stats.len(), csv_path.display(), summary_path.display());
+        }
+
         "word" => {
-            if args.len() < 1 {
+            if args.is_empty() {
                 return print_usage("word 'word' not provided.");
             }
 
-            let guess = &args[0];
+            let guess = match read_guess(&args[0], &dictionary) {
+                Ok(guess) => guess,
+                Err(error) => return print_error(&error),
+            };
+
+            let guess = &guess;
+            let report = word_report(&scorer, guess, &answers, dictionary.is_allowed_guess(guess));
+            if print_formatted(&report, format) {
+                return ExitCode::SUCCESS;
+            }
+
             println!("Answer Distances from '{guess}':\n");
 
-            let map = word_distance_map(&guess, ANSWERS);
-            let mut distances = map.keys().collect::<Vec<_>>();
-            distances.sort();
+            if !report.allowed {
+                println!("Note: '{guess}' is not in the allowed guess list.\n");
+            }
 
-            // Iterate over map in increasing distance order
-            for distance in distances.iter() {
-                let answers = map.get(distance).unwrap();
-                for answer in answers {
-                    println!("{distance:05} -> {answer}");
-                }
+            // Answers are in increasing distance order
+            for answer in report.answers.iter() {
+                println!("{} -> {}", answer.score, answer.answer);
             }
 
-            let cv = map_to_cv(&map);
-            let cv = cv_to_string(&cv);
-            println!("\n {} distinct responses.\n CV: {}", distances.len(), cv);
+            println!("\n {} distinct responses.\n CV: {}", report.distinct, cv_to_string(&report.cv));
 
-            let table = letter_table(&guess, &ANSWERS);
-            println!("\n{}", table);
+            match scorer.letter_table(guess, &answers) {
+                Ok(table) => println!("\n{}", table),
+                Err(error) => return print_error(&error),
+            }
         }
 
         "lo" | "letter_options" => {
@@ -77,12 +278,20 @@ fn main() -> ExitCode {
                 return print_usage("letter_options 'guess' 'score' not provided.");
             }
 
-            let guess = args[0].to_ascii_lowercase();
-            let score = args[1].parse::<u32>().unwrap();
+            let (guess, score) = match read_guess(&args[0], &dictionary).and_then(|guess| Ok((guess, args[1].parse::<Score>()?))) {
+                Ok(turn) => turn,
+                Err(error) => return print_error(&error),
+            };
+
+            let frequencies = priors.letter_frequencies(&answers);
+            if print_formatted(&letter_options_report(&scorer, &guess, &score, &frequencies), format) {
+                return ExitCode::SUCCESS;
+            }
 
-            let frequencies = letter_frequencies(ANSWERS);
-            let options = letter_options(&guess, score, &frequencies);
-            println!("{}", options);
+            match scorer.letter_options(&guess, &score, &frequencies) {
+                Ok(options) => println!("{}", options),
+                Err(error) => return print_error(&error),
+            }
         }
 
         "ao" | "answer_options" => {
@@ -90,13 +299,587 @@ fn main() -> ExitCode {
                 return print_usage("answer_options 'guess' 'score' not provided.");
             }
 
-            let guess = args[0].to_ascii_lowercase();
-            let score = args[1].parse::<u32>().unwrap();
-            let within: u32 = args.get(2).map(|s| s.parse().unwrap()).unwrap_or(2);
+            let (guess, score) = match read_turn(&args[0], &args[1], &dictionary) {
+                Ok(turn) => turn,
+                Err(error) => return print_error(&error),
+            };
+
+            let within = match args.get(2).map(|within| within.parse::<u32>()) {
+                None => 2,
+                Some(Ok(within)) => within,
+                Some(Err(_)) => return print_usage(&format!("answer_options 'within' must be a number, not '{}'.", args[2])),
+            };
+
+            let report = answer_options_report(&scorer, &guess, &score, &answers, within, &priors);
+            if print_formatted(&report, format) {
+                return ExitCode::SUCCESS;
+            }
+
+            for option in report.answers.iter() {
+                println!("{}: {} ({})", option.distance, option.answer, option.score);
+            }
+        }
+
+        "lm" | "letters" => {
+            if args.len() < 2 || !args.len().is_multiple_of(2) {
+                return print_usage("letters 'guess' 'score' pairs not provided.");
+            }
+
+            let mut turns = Vec::new();
+            for turn in args.chunks(2) {
+                match read_turn(&turn[0], &turn[1], &dictionary) {
+                    Ok(turn) => turns.push(turn),
+                    Err(error) => return print_error(&error),
+                }
+            }
+
+            let matrix = LetterMatrix::new(&scorer, &turns, &answers, &priors);
+            if print_formatted(&matrix, format) {
+                return ExitCode::SUCCESS;
+            }
+
+            println!("{}", matrix.to_table());
+            println!("{} answers remain.", matrix.candidates);
+        }
+
+        "fz" | "fuzzy" => {
+            if args.len() < 2 || !args.len().is_multiple_of(2) {
+                return print_usage("fuzzy 'guess' 'score' pairs not provided.");
+            }
+
+            let mut turns = Vec::new();
+            for turn in args.chunks(2) {
+                match read_guess(&turn[0], &dictionary).and_then(|guess| Ok((guess, turn[1].parse::<FuzzyScore>()?))) {
+                    Ok(turn) => turns.push(turn),
+                    Err(error) => return print_error(&error),
+                }
+            }
+
+            let turns = turns.iter().map(|(guess, score)| (guess.as_str(), score.clone())).collect::<Vec<_>>();
+            let matches = fuzzy_matches(&scorer, &turns, &answers, &priors);
+            if print_formatted(&matches, format) {
+                return ExitCode::SUCCESS;
+            }
+
+            for m in matches.iter().take(10) {
+                let scores = m.scores.iter().map(|score| score.to_string()).collect::<Vec<_>>();
+                println!("{:6.2}%: {} ({})", m.probability * 100.0, m.answer, scores.join(", "));
+            }
+        }
+
+        "solve" => {
+            let matrix = match build_matrix(&scorer, &guesses, &answers, cache_path.as_deref()) {
+                Ok(matrix) => matrix,
+                Err(error) => return print_error(&error),
+            };
+
+            let mut solver = Solver::new(&matrix);
+            solver.set_rank_by(rank_by);
+            solver.set_hard_mode(hard_mode);
+            solver.set_priors(&priors);
+
+            for turn in args.chunks(2) {
+                if turn.len() < 2 {
+                    return print_usage("solve 'score' not provided for last guess.");
+                }
+
+                let (guess, score) = match read_turn(&turn[0], &turn[1], &dictionary) {
+                    Ok(turn) => turn,
+                    Err(error) => return print_error(&error),
+                };
+
+                let violations = if hard_mode { solver.hard_mode_violations(&guess) } else { Vec::new() };
+                if let Some(violation) = violations.first() {
+                    return print_error(&format!("'{guess}' is not allowed in hard mode. {violation}"));
+                }
+
+                if let Err(error) = solver.add_turn(&guess, score) {
+                    return print_error(&error);
+                }
+
+                if format == Format::Text {
+                    print_solver(&solver);
+                }
+            }
+
+            // Structured formats report the turns given, without asking for more
+            if print_formatted(&solve_report(&solver, 10), format) {
+                return ExitCode::SUCCESS;
+            }
+
+            while !solver.is_solved() && !solver.candidates().is_empty() {
+                let Some(line) = read_line("\n<guess> <score>: ") else {
+                    break;
+                };
+
+                // The score may be written with separators (ex: papal 0 0 2 6 5), so everything after the guess is the score
+                let Some((guess, score)) = line.trim().split_once(char::is_whitespace) else {
+                    if line.trim().is_empty() {
+                        break;
+                    }
+
+                    println!("Enter a guess and score, like 'papal 00265'.");
+                    continue;
+                };
+
+                let (guess, score) = match read_turn(guess, score, &dictionary) {
+                    Ok(turn) => turn,
+                    Err(error) => {
+                        println!("{error}");
+                        continue;
+                    }
+                };
+
+                let violations = if hard_mode { solver.hard_mode_violations(&guess) } else { Vec::new() };
+                if !violations.is_empty() {
+                    println!("'{guess}' is not allowed in hard mode:");
+                    for violation in violations.iter() {
+                        println!("  {violation}");
+                    }
+
+                    continue;
+                }
+
+                match solver.add_turn(&guess, score) {
+                    Ok(()) => print_solver(&solver),
+                    Err(error) => println!("{error}"),
+                }
+            }
+        }
+
+        "boards" => {
+            let count = match args.first().map(|text| text.parse::<usize>()) {
+                Some(Ok(count)) if count > 0 => count,
+                Some(_) => return print_usage("boards 'count' must be a number of boards, like 4."),
+                None => return print_usage("boards 'count' not provided."),
+            };
+
+            let matrix = match build_matrix(&scorer, &guesses, &answers, cache_path.as_deref()) {
+                Ok(matrix) => matrix,
+                Err(error) => return print_error(&error),
+            };
+
+            let mut solver = MultiSolver::new(&matrix, count);
+            solver.set_rank_by(rank_by);
+
+            // Each turn is a guess followed by a score for each board still being played
+            let mut turns = &args[1..];
+            while let Some(guess) = turns.first() {
+                let needed = solver.playing().len();
+                if needed == 0 || turns.len() < 1 + needed {
+                    return print_usage(&format!("boards '{guess}' needs {needed} scores, one for each board still being played."));
+                }
+
+                if let Err(error) = read_boards_turn(guess, &turns[1..=needed], &dictionary).and_then(|(guess, scores)| solver.add_turn(&guess, &scores)) {
+                    return print_error(&error);
+                }
+
+                turns = &turns[1 + needed..];
+            }
+
+            if print_formatted(&boards_report(&solver, 10), format) {
+                return ExitCode::SUCCESS;
+            }
+
+            print_boards(&solver);
+            while !solver.is_done() {
+                let Some(line) = read_line(&format!("\n<guess> <score> x{}: ", solver.playing().len())) else {
+                    break;
+                };
+
+                let words = line.split_whitespace().collect::<Vec<_>>();
+                let Some((guess, scores)) = words.split_first() else {
+                    break;
+                };
+
+                match read_boards_turn(guess, scores, &dictionary).and_then(|(guess, scores)| solver.add_turn(&guess, &scores)) {
+                    Ok(()) => print_boards(&solver),
+                    Err(error) => println!("{error}"),
+                }
+            }
+        }
+
+        "assist" => {
+            let color = env::var_os("NO_COLOR").is_none();
+            let mut assistant = Assistant::new(&scorer, &answers, color);
+
+            if let Err(error) = assistant.run(&mut io::stdin().lock(), &mut io::stdout()) {
+                return print_usage(&format!("Assist failed: {error}"));
+            }
+        }
+
+        "play" => {
+            let daily = seed.is_none() && !evil;
+            let game = match seed {
+                _ if evil => Game::evil(&dictionary, &scorer),
+                Some(seed) => match seed.parse::<u64>() {
+                    Ok(seed) => Game::seeded(&dictionary, &scorer, seed),
+                    Err(_) => return print_usage("--seed must be a number."),
+                },
+                None => match schedule_path.as_deref() {
+                    Some(path) => read_file(path).and_then(|text| parse_schedule(&text)).and_then(|schedule| Game::scheduled(&dictionary, &scorer, &schedule, date)),
+                    None => Game::daily(&dictionary, &scorer, date),
+                },
+            };
+
+            let mut game = match game {
+                Ok(game) => game,
+                Err(error) => return print_error(&error),
+            };
+
+            let color = env::var_os("NO_COLOR").is_none();
+
+            while game.status() == GameStatus::Playing {
+                let Some(line) = read_line(&format!("\nGuess {} of {MAX_GUESSES}: ", game.turns().len() + 1)) else {
+                    break;
+                };
+
+                match game.guess(&line) {
+                    Ok(score) => println!("{}  {score}", render_score(&line.trim().to_ascii_lowercase(), &score, color)),
+                    Err(error) => println!("{error}"),
+                }
+            }
+
+            match game.status() {
+                GameStatus::Won => println!("\nSolved in {}/{MAX_GUESSES}!", game.turns().len()),
+                _ => println!("\nThe answer was '{}'.", game.answer()),
+            }
+
+            // Record finished games (not those abandoned at the end of input) for stats
+            if let Some(path) = history_path.as_ref().filter(|_| game.status() != GameStatus::Playing) {
+                if let Err(error) = append_record(Path::new(path), &GameRecord::from_game(&game, daily.then_some(date))) {
+                    return print_error(&error);
+                }
+            }
+        }
+
+        "record" => {
+            if args.len() < 3 {
+                return print_usage("record needs the 'answer', then each 'guess' and 'score'.");
+            }
+
+            let answer = match read_guess(&args[0], &dictionary) {
+                Ok(answer) => answer,
+                Err(error) => return print_error(&error),
+            };
+
+            let mut turns = Vec::new();
+            for turn in args[1..].chunks(2) {
+                if turn.len() < 2 {
+                    return print_usage("record 'score' not provided for last guess.");
+                }
+
+                let (guess, score) = match read_turn(&turn[0], &turn[1], &dictionary) {
+                    Ok(turn) => turn,
+                    Err(error) => return print_error(&error),
+                };
+
+                let expected = match scorer.word_distance(&guess, &answer) {
+                    Ok(expected) => expected,
+                    Err(error) => return print_error(&error),
+                };
+
+                if expected != score {
+                    return print_error(&format!("'{guess}' against '{answer}' scores {expected}, not {score}. Check the --layout and --metric match the game."));
+                }
+
+                turns.push((guess, score));
+            }
+
+            let record = GameRecord { date: Some(date), answer, turns };
+            let path = history_path.as_deref().unwrap_or(DEFAULT_HISTORY_PATH);
+            if let Err(error) = record.validate().and_then(|_| append_record(Path::new(path), &record)) {
+                return print_error(&error);
+            }
+
+            println!("Recorded {} on {date} ({}) in '{path}'.", record.answer, if record.won() { "won" } else { "not solved" });
+        }
+
+        "stats" => {
+            let path = history_path.as_deref().unwrap_or(DEFAULT_HISTORY_PATH);
+            let history = match load_history(Path::new(path)) {
+                Ok(history) => history,
+                Err(error) => return print_error(&error),
+            };
+
+            let matrix = match build_matrix(&scorer, &guesses, &answers, cache_path.as_deref()) {
+                Ok(matrix) => matrix,
+                Err(error) => return print_error(&error),
+            };
+
+            let mut games = Vec::new();
+            for (index, record) in history.iter().enumerate() {
+                match review_game(&matrix, record, rank_by) {
+                    Ok(review) => games.push(review),
+                    Err(error) => return print_error(&format!("Game {} ({}): {error}", index + 1, record.answer)),
+                }
+            }
+
+            let report = StatsReport { summary: summarize(&games), games };
+            if print_formatted(&report, format) {
+                return ExitCode::SUCCESS;
+            }
+
+            for game in report.games.iter() {
+                let date = game.date.map(|date| format!("{date} ")).unwrap_or_default();
+                let outcome = if game.won { format!("solved in {}", game.turns.len()) } else { "not solved".to_string() };
+                println!("\n{date}{}: {outcome}", game.answer);
+
+                for turn in game.turns.iter() {
+                    let recommended = match (turn.recommended, turn.recommended_after) {
+                        (Some(word), Some(after)) => format!(" (recommended {word} -> {after})"),
+                        _ => String::new(),
+                    };
+
+                    println!("  {} {}: {} -> {} answers{recommended}", turn.guess, turn.score, turn.candidates_before, turn.candidates_after);
+                }
+            }
+
+            let summary = &report.summary;
+            let histogram = summary.histogram.iter().map(|count| *count as u32).collect::<Vec<_>>();
+            println!("\n{} games, {} solved, average {:.2} guesses when solved. Solved per guess: {}", summary.games, summary.won, summary.average_guesses, cv_to_string(&histogram));
+            println!("{} turns left more answers than the recommended guess would have.", summary.behind_turns);
+        }
+
+        "pairs" | "openers" => {
+            let length = match (mode, args.first()) {
+                ("pairs", _) | (_, None) => 2,
+                (_, Some(words)) => match words.parse::<usize>() {
+                    Ok(length @ 2..=3) => length,
+                    _ => return print_usage("openers can find openers of 2 or 3 words."),
+                },
+            };
+
+            let pool_size = match pool_size.map(|size| size.parse::<usize>()) {
+                None => 100,
+                Some(Ok(size)) if size > 0 => size,
+                Some(_) => return print_usage("--pool must be a positive number."),
+            };
+
+            let matrix = match build_matrix(&scorer, &guesses, &answers, cache_path.as_deref()) {
+                Ok(matrix) => matrix,
+                Err(error) => return print_error(&error),
+            };
+
+            let pool = opener_pool(&matrix, pool_size);
+            let openers = find_openers(&matrix, length, &pool, 10, &mut |done, total, best| {
+                let best = best.map(|b| format!("{} (worst case {})", b.guesses.join(", "), b.worst_case)).unwrap_or_default();
+                eprint!("\r{done}/{total} first words searched. Best: {best}    ");
+            });
+            eprintln!();
+
+            if print_formatted(&openers, format) {
+                return ExitCode::SUCCESS;
+            }
+
+            for opener in openers.iter() {
+                println!("{}: worst case {}, {} distinct", opener.guesses.join(", "), opener.worst_case, opener.distinct);
+            }
+
+            if openers.first().is_some_and(|best| best.worst_case == 1) {
+                println!("These openers identify every answer.");
+            }
+        }
+
+        "tree" => {
+            if args.is_empty() {
+                return print_usage("tree 'opener' not provided.");
+            }
+
+            if out_path.is_some() && args.len() > 1 {
+                return print_usage("tree --out can only be used with one opener.");
+            }
+
+            let matrix = match build_matrix(&scorer, &guesses, &answers, cache_path.as_deref()) {
+                Ok(matrix) => matrix,
+                Err(error) => return print_error(&error),
+            };
+
+            let mut summaries = Vec::new();
+
+            for opener in args {
+                let opener = match read_guess(opener, &dictionary) {
+                    Ok(opener) => opener,
+                    Err(error) => return print_error(&error),
+                };
+
+                let tree = match DecisionTree::build(&matrix, &opener, rank_by) {
+                    Ok(tree) => tree,
+                    Err(error) => return print_error(&error),
+                };
+
+                let stats = tree.stats();
+
+                if let Some(path) = out_path.as_ref() {
+                    if let Err(error) = fs::write(path, tree.to_json()) {
+                        return print_error(&QwertleError::Io { path: path.clone(), message: error.to_string() });
+                    }
+                }
+
+                summaries.push(TreeSummary { opener, stats });
+            }
+
+            if print_formatted(&summaries, format) {
+                return ExitCode::SUCCESS;
+            }
+
+            for TreeSummary { opener, stats } in summaries.iter() {
+                let histogram = stats.histogram.iter().map(|count| *count as u32).collect::<Vec<_>>();
+                println!("{opener}: max {} guesses, average {:.4} over {} answers. Solved per guess: {}", stats.max_guesses, stats.average_guesses, stats.answers, cv_to_string(&histogram));
+
+                if stats.max_guesses > 6 {
+                    println!("  Not always solvable in six guesses.");
+                }
+
+                if let Some(path) = out_path.as_ref() {
+                    println!("Tree written to '{path}'.");
+                }
+            }
+        }
+
+        "minimax" => {
+            let matrix = match build_matrix(&scorer, &guesses, &answers, cache_path.as_deref()) {
+                Ok(matrix) => matrix,
+                Err(error) => return print_error(&error),
+            };
+
+            let opener = match args.first() {
+                Some(opener) => match read_guess(opener, &dictionary) {
+                    Ok(opener) => opener,
+                    Err(error) => return print_error(&error),
+                },
+                None => match minimax_opener(&matrix) {
+                    Some(opener) => opener.to_string(),
+                    None => return print_error(&"There are no answers to solve."),
+                },
+            };
 
-            let options = answer_options(&guess, score, ANSWERS, within);
-            for (distance, word, score) in options {
-                println!("{distance}: {word} ({score:05})");
+            let stats = match minimax_bound(&matrix, &opener) {
+                Ok(stats) => stats,
+                Err(error) => return print_error(&error),
+            };
+
+            let summaries = vec![TreeSummary { opener, stats }];
+            if print_formatted(&summaries, format) {
+                return ExitCode::SUCCESS;
+            }
+
+            let TreeSummary { opener, stats } = &summaries[0];
+            let histogram = stats.histogram.iter().map(|count| *count as u32).collect::<Vec<_>>();
+            println!("Minimax from '{opener}' solves every answer in at most {} guesses (average {:.4}). Solved per guess: {}", stats.max_guesses, stats.average_guesses, cv_to_string(&histogram));
+        }
+
+        "decode" => {
+            if args.is_empty() {
+                return print_usage("decode 'image' or 'color' not provided.");
+            }
+
+            let table = match colors_path {
+                None => ColorTable::qwertle(),
+                Some(path) => match read_file(&path).and_then(|text| ColorTable::parse(&text)) {
+                    Ok(table) => table,
+                    Err(error) => return print_error(&error),
+                },
+            };
+
+            let tiles = if args[0].to_ascii_lowercase().ends_with(".png") {
+                fs::read(&args[0])
+                    .map_err(|e| QwertleError::Io { path: args[0].clone(), message: e.to_string() })
+                    .and_then(|bytes| Image::from_png(&bytes))
+                    .map(|image| read_image(&table, &image))
+            } else {
+                args.iter().map(|color| color.parse::<Rgb>()).collect::<Result<Vec<_>, _>>().and_then(|colors| read_colors(&table, &colors))
+            };
+
+            let report = match tiles.and_then(|tiles| Ok(DecodeReport { score: tiles_to_score(&tiles)?, tiles })) {
+                Ok(report) => report,
+                Err(error) => return print_error(&error),
+            };
+
+            if print_formatted(&report, format) {
+                return ExitCode::SUCCESS;
+            }
+
+            println!("{}", report.score);
+        }
+
+        "check" => {
+            if args.is_empty() {
+                return print_usage("check 'path' not provided.");
+            }
+
+            let recorded = match read_file(&args[0]).and_then(|text| parse_recorded_scores(&text)) {
+                Ok(recorded) => recorded,
+                Err(error) => return print_error(&error),
+            };
+
+            let report = check_report(&scorer, &recorded);
+            if print_formatted(&report, format) {
+                return ExitCode::SUCCESS;
+            }
+
+            for m in report.mismatches.iter() {
+                println!("{} -> {}: recorded {}, computed {}", m.guess, m.answer, m.recorded, m.computed);
+            }
+
+            println!("{} of {} recorded scores match ({} on {}).", report.matching, report.recorded, report.layout, report.metric);
+        }
+
+        "simulate" => {
+            let names = if args.is_empty() { vec!["entropy".to_string()] } else { args.to_vec() };
+
+            let matrix = match build_matrix(&scorer, &guesses, &answers, cache_path.as_deref()) {
+                Ok(matrix) => matrix,
+                Err(error) => return print_error(&error),
+            };
+
+            let mut strategies = Vec::new();
+            for name in names.iter() {
+                match parse_strategy(name, &matrix) {
+                    Ok(strategy) => strategies.push(strategy),
+                    Err(error) => return print_error(&error),
+                }
+            }
+
+            let mut summaries = Vec::new();
+
+            for strategy in strategies.iter() {
+                let simulation = simulate(&matrix, strategy.as_ref());
+                let summary = SimulationSummary::new(&simulation);
+
+                if format == Format::Text {
+                    let histogram = summary.histogram.iter().map(|count| *count as u32).collect::<Vec<_>>();
+                    let failures = simulation.failures();
+
+                    println!("{}: max {} guesses, average {:.4}, {} failures in {:.2}s. Solved per guess: {}", summary.strategy, summary.max_guesses, summary.average_guesses, failures.len(), summary.seconds, cv_to_string(&histogram));
+
+                    for game in failures.iter().take(10) {
+                        println!("  {}: {}", game.answer, game.guesses.join(", "));
+                    }
+
+                    if failures.len() > 10 {
+                        println!("  ... and {} more.", failures.len() - 10);
+                    }
+                }
+
+                summaries.push(summary);
+            }
+
+            print_formatted(&summaries, format);
+        }
+
+        "serve" => {
+            let address = args.first().map(|a| a.as_str()).unwrap_or("127.0.0.1:8080");
+            let server = Server::new(&scorer, &answers);
+
+            let listener = match TcpListener::bind(address) {
+                Ok(listener) => listener,
+                Err(error) => return print_usage(&format!("Unable to serve on '{address}': {error}")),
+            };
+
+            println!("Listening on http://{}...", listener.local_addr().map_or(address.to_string(), |a| a.to_string()));
+            if let Err(error) = server.run(listener, &mut io::stdout()) {
+                return print_error(&format!("Server stopped: {error}"));
             }
         }
 
@@ -108,6 +891,168 @@ fn main() -> ExitCode {
     ExitCode::SUCCESS
 }
 
+// Show the remaining answers (up to a limit) and the recommended next guess.
+fn print_solver(solver: &Solver) {
+    const SHOW_LIMIT: usize = 50;
+
+    let report = solve_report(solver, 5);
+    let candidates = &report.candidates;
+    if let Some(turn) = report.turns.last() {
+        println!("\n{} {} -> {} answers remain:", turn.guess, turn.score, candidates.len());
+    }
+
+    // Show each answer's chance when some are more likely than others
+    let weighted = report.probabilities.windows(2).any(|pair| pair[0] != pair[1]);
+    for (answer, probability) in candidates.iter().zip(report.probabilities.iter()).take(SHOW_LIMIT) {
+        if weighted {
+            println!("  {answer} ({:.1}%)", probability * 100.0);
+        } else {
+            println!("  {answer}");
+        }
+    }
+
+    if candidates.len() > SHOW_LIMIT {
+        println!("  ... and {} more.", candidates.len() - SHOW_LIMIT);
+    }
+
+    if report.solved {
+        println!("Solved: {}", candidates[0]);
+    } else if candidates.is_empty() {
+        println!("No answers match all scores. Check the guesses and scores entered.");
+    } else if report.recommendations.is_empty() {
+        println!("Recommended next guess: {}", candidates[0]);
+    } else {
+        println!("\nRecommended next guesses:");
+        println!("  Guess  Distinct  Entropy  Expected  Worst");
+
+        for rating in report.recommendations.iter() {
+            let marker = if rating.is_candidate { "*" } else { " " };
+            println!("  {}{marker} {:>8}  {:>7.3}  {:>8.3}  {:>5}", rating.guess, rating.distinct, rating.entropy, rating.expected_size, rating.worst_case);
+        }
+    }
+}
+
+// Show each board's remaining answers and the recommended next guess across the boards.
+fn print_boards(solver: &MultiSolver) {
+    const SHOW_LIMIT: usize = 10;
+
+    let report = boards_report(solver, 5);
+    for (index, board) in report.boards.iter().enumerate() {
+        let candidates = &board.candidates;
+        if board.finished {
+            println!("\nBoard {}: solved ({})", index + 1, board.turns.last().map_or("", |turn| turn.guess.as_str()));
+            continue;
+        }
+
+        let shown = candidates.iter().take(SHOW_LIMIT).copied().collect::<Vec<_>>().join(", ");
+        let more = if candidates.len() > SHOW_LIMIT { format!(", ... and {} more", candidates.len() - SHOW_LIMIT) } else { String::new() };
+        println!("\nBoard {}: {} answers remain: {shown}{more}", index + 1, candidates.len());
+    }
+
+    if solver.is_done() {
+        println!("\nAll {} boards solved.", report.boards.len());
+    } else if report.boards.iter().any(|board| !board.finished && board.candidates.is_empty()) {
+        println!("\nNo answers match all scores on a board. Check the guesses and scores entered.");
+    } else if report.recommendations.is_empty() {
+        println!("\nRecommended next guess: {}", report.recommendation.unwrap_or_default());
+    } else {
+        println!("\nRecommended next guesses:");
+        println!("  Guess  Distinct  Entropy  Expected  Worst");
+
+        for rating in report.recommendations.iter() {
+            let marker = if rating.is_candidate { "*" } else { " " };
+            println!("  {}{marker} {:>8}  {:>7.3}  {:>8.3}  {:>5}", rating.guess, rating.distinct, rating.entropy, rating.expected_size, rating.worst_case);
+        }
+    }
+}
+
+// Print results as JSON, CSV, or Markdown. Returns false for text, which each mode prints itself.
+fn print_formatted<T: Serialize + Table>(results: &T, format: Format) -> bool {
+    match render(results, format) {
+        Some(text) => {
+            print!("{text}");
+            true
+        }
+        None => false,
+    }
+}
+
+// Score every guess against every answer, reusing the --cache file if it matches.
+fn build_matrix<'a>(scorer: &Scorer, guesses: &[&'a str], answers: &[&'a str], cache_path: Option<&str>) -> Result<ScoreMatrix<'a>, QwertleError> {
+    match cache_path {
+        None => ScoreMatrix::build(scorer, guesses, answers),
+        Some(path) => ScoreMatrix::load_or_build(Path::new(path), scorer, guesses, answers),
+    }
+}
+
+fn read_file(path: &str) -> Result<String, QwertleError> {
+    fs::read_to_string(path).map_err(|e| QwertleError::Io { path: path.to_string(), message: e.to_string() })
+}
+
+// Check a guess typed by the user is a word, and the length of the answers if there are any.
+fn read_guess(text: &str, dictionary: &Dictionary) -> Result<String, QwertleError> {
+    match dictionary.word_length() {
+        Some(length) => validate_word_length(text, length),
+        None => validate_word(text),
+    }
+}
+
+// Check a guess and its score typed by the user, including that the score has a distance for each letter.
+fn read_turn(guess: &str, score: &str, dictionary: &Dictionary) -> Result<(String, Score), QwertleError> {
+    let guess = read_guess(guess, dictionary)?;
+    let score = score.parse::<Score>()?;
+    validate_score(&guess, &score)?;
+    Ok((guess, score))
+}
+
+// Check a guess and its score on each board still being played, typed by the user.
+fn read_boards_turn<S: AsRef<str>>(guess: &str, scores: &[S], dictionary: &Dictionary) -> Result<(String, Vec<Score>), QwertleError> {
+    let guess = read_guess(guess, dictionary)?;
+    let scores = scores.iter().map(|score| score.as_ref().parse::<Score>()).collect::<Result<Vec<_>, _>>()?;
+    Ok((guess, scores))
+}
+
+// Prompt for a line of input, returning None at the end of input.
+fn read_line(prompt: &str) -> Option<String> {
+    print!("{prompt}");
+    io::stdout().flush().ok()?;
+
+    let mut line = String::new();
+    match io::stdin().lock().read_line(&mut line) {
+        Ok(0) | Err(_) => None,
+        Ok(_) => Some(line),
+    }
+}
+
+// Remove a '--name' flag from the arguments, returning whether it was present.
+fn take_flag(args: &mut Vec<String>, name: &str) -> bool {
+    match args.iter().position(|a| a.eq_ignore_ascii_case(name)) {
+        Some(index) => {
+            args.remove(index);
+            true
+        }
+        None => false,
+    }
+}
+
+// Remove a '--name value' option from the arguments, returning the value if it was present.
+fn take_option(args: &mut Vec<String>, name: &str) -> Option<String> {
+    let index = args.iter().position(|a| a.eq_ignore_ascii_case(name))?;
+    args.remove(index);
+
+    if index < args.len() {
+        Some(args.remove(index))
+    } else {
+        Some(String::new())
+    }
+}
+
+// Report a problem with the input (ex: a typo in a guess or score). The usage is left out, since the arguments were understood.
+fn print_error(error: &dyn fmt::Display) -> ExitCode {
+    println!("ERROR: {error}");
+    ExitCode::FAILURE
+}
+
 fn print_usage(error: &str) -> ExitCode {
     println!("ERROR:\n  {}", error);
     println!("\n{}", USAGE);
@@ -0,0 +1,355 @@
+use std::{collections::HashMap, fs, io, path::Path, thread};
+use crate::{distance::Scorer, error::*, score::Score};
+
+// Cache file layout: magic, version, fingerprint of the scorer and word lists, guess and answer counts,
+// then the distinct score count for each guess (u32) and every cell id (u16), all little-endian.
+const CACHE_MAGIC: &[u8; 4] = b"QWSM";
+const CACHE_VERSION: u32 = 1;
+
+// Every allowed guess scored against every answer, computed once (in parallel) and shared by the analysis modes.
+// Each cell holds a small id for the score, numbered in order of first appearance in the guess row.
+// Equal ids within a row mean equal scores, which is all that grouping answers needs.
+pub struct ScoreMatrix<'a> {
+    scorer: Scorer,
+    guesses: Vec<&'a str>,
+    answers: Vec<&'a str>,
+    guess_lookup: HashMap<&'a str, usize>,
+    answer_lookup: HashMap<&'a str, usize>,
+    distinct: Vec<u32>,
+    ids: Vec<u16>,
+}
+
+impl<'a> ScoreMatrix<'a> {
+    // Score every guess against every answer, splitting the guesses across all cores.
+    // Every word must be letters a-z and the same length, and score ids are u16s, so there may be at most u16::MAX answers.
+    pub fn build(scorer: &Scorer, guesses: &[&'a str], answers: &[&'a str]) -> Result<ScoreMatrix<'a>, QwertleError> {
+        if answers.len() > u16::MAX as usize {
+            return Err(QwertleError::InvalidInput(format!("A score matrix supports at most {} answers, not {}.", u16::MAX, answers.len())));
+        }
+
+        if let Some(length) = answers.first().or(guesses.first()).map(|word| word.len()) {
+            for word in answers.iter().chain(guesses.iter()) {
+                validate_word_length(word, length)?;
+            }
+        }
+
+        let mut distinct = vec![0u32; guesses.len()];
+        let mut ids = vec![0u16; guesses.len() * answers.len()];
+
+        let threads = thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+
+        // Score on this thread when there's only one core, or threads aren't supported (as in WebAssembly)
+        if threads == 1 {
+            for ((guess, row), distinct) in guesses.iter().zip(ids.chunks_mut(answers.len().max(1))).zip(distinct.iter_mut()) {
+                *distinct = score_row(scorer, guess, answers, row);
+            }
+        } else if !answers.is_empty() {
+            let rows_per_thread = guesses.len().div_ceil(threads).max(1);
+
+            thread::scope(|scope| {
+                let row_chunks = ids.chunks_mut(rows_per_thread * answers.len());
+                let distinct_chunks = distinct.chunks_mut(rows_per_thread);
+
+                for ((guess_chunk, row_chunk), distinct_chunk) in guesses.chunks(rows_per_thread).zip(row_chunks).zip(distinct_chunks) {
+                    scope.spawn(move || {
+                        for ((guess, row), distinct) in guess_chunk.iter().zip(row_chunk.chunks_mut(answers.len())).zip(distinct_chunk.iter_mut()) {
+                            *distinct = score_row(scorer, guess, answers, row);
+                        }
+                    });
+                }
+            });
+        }
+
+        Ok(ScoreMatrix::from_parts(scorer, guesses, answers, distinct, ids))
+    }
+
+    fn from_parts(scorer: &Scorer, guesses: &[&'a str], answers: &[&'a str], distinct: Vec<u32>, ids: Vec<u16>) -> ScoreMatrix<'a> {
+        ScoreMatrix {
+            scorer: scorer.clone(),
+            guesses: guesses.to_vec(),
+            answers: answers.to_vec(),
+            guess_lookup: guesses.iter().enumerate().map(|(i, w)| (*w, i)).collect(),
+            answer_lookup: answers.iter().enumerate().map(|(i, w)| (*w, i)).collect(),
+            distinct,
+            ids,
+        }
+    }
+
+    // Load the matrix from a cache file if it was built with the same scorer and words, otherwise build it and save it there.
+    pub fn load_or_build(path: &Path, scorer: &Scorer, guesses: &[&'a str], answers: &[&'a str]) -> Result<ScoreMatrix<'a>, QwertleError> {
+        if let Ok(matrix) = ScoreMatrix::load(path, scorer, guesses, answers) {
+            return Ok(matrix);
+        }
+
+        let matrix = ScoreMatrix::build(scorer, guesses, answers)?;
+        matrix.save(path).map_err(|e| QwertleError::Io { path: path.display().to_string(), message: e.to_string() })?;
+        Ok(matrix)
+    }
+
+    // Write the matrix to a binary cache file.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut bytes = Vec::with_capacity(32 + self.distinct.len() * 4 + self.ids.len() * 2);
+        bytes.extend_from_slice(CACHE_MAGIC);
+        bytes.extend_from_slice(&CACHE_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&fingerprint(&self.scorer, &self.guesses, &self.answers).to_le_bytes());
+        bytes.extend_from_slice(&(self.guesses.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&(self.answers.len() as u32).to_le_bytes());
+
+        for distinct in self.distinct.iter() {
+            bytes.extend_from_slice(&distinct.to_le_bytes());
+        }
+
+        for id in self.ids.iter() {
+            bytes.extend_from_slice(&id.to_le_bytes());
+        }
+
+        fs::write(path, bytes)
+    }
+
+    // Read a matrix from a binary cache file, failing if it was built with a different scorer or word lists.
+    pub fn load(path: &Path, scorer: &Scorer, guesses: &[&'a str], answers: &[&'a str]) -> io::Result<ScoreMatrix<'a>> {
+        let bytes = fs::read(path)?;
+        let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, format!("Score cache '{}' {message}.", path.display()));
+
+        let header_length = 4 + 4 + 8 + 4 + 4;
+        if bytes.len() < header_length || &bytes[0..4] != CACHE_MAGIC {
+            return Err(invalid("is not a score cache"));
+        }
+
+        let read_u32 = |at: usize| u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap());
+        let read_u64 = |at: usize| u64::from_le_bytes(bytes[at..at + 8].try_into().unwrap());
+
+        if read_u32(4) != CACHE_VERSION {
+            return Err(invalid("is from a different version"));
+        }
+
+        if read_u64(8) != fingerprint(scorer, guesses, answers) || read_u32(16) as usize != guesses.len() || read_u32(20) as usize != answers.len() {
+            return Err(invalid("was built for different words or scoring"));
+        }
+
+        let cells = guesses.len() * answers.len();
+        if bytes.len() != header_length + guesses.len() * 4 + cells * 2 {
+            return Err(invalid("is truncated"));
+        }
+
+        let distinct = bytes[header_length..header_length + guesses.len() * 4]
+            .chunks_exact(4)
+            .map(|b| u32::from_le_bytes(b.try_into().unwrap()))
+            .collect();
+
+        let ids = bytes[header_length + guesses.len() * 4..]
+            .chunks_exact(2)
+            .map(|b| u16::from_le_bytes(b.try_into().unwrap()))
+            .collect();
+
+        Ok(ScoreMatrix::from_parts(scorer, guesses, answers, distinct, ids))
+    }
+
+    pub fn scorer(&self) -> &Scorer {
+        &self.scorer
+    }
+
+    pub fn guesses(&self) -> &[&'a str] {
+        &self.guesses
+    }
+
+    pub fn answers(&self) -> &[&'a str] {
+        &self.answers
+    }
+
+    pub fn guess_index(&self, word: &str) -> Option<usize> {
+        self.guess_lookup.get(word).copied()
+    }
+
+    pub fn answer_index(&self, word: &str) -> Option<usize> {
+        self.answer_lookup.get(word).copied()
+    }
+
+    // How many different scores a guess can get across all answers.
+    pub fn distinct(&self, guess: usize) -> usize {
+        self.distinct[guess] as usize
+    }
+
+    // The score id of a guess against each answer.
+    pub fn row(&self, guess: usize) -> &[u16] {
+        let start = guess * self.answers.len();
+        &self.ids[start..start + self.answers.len()]
+    }
+
+    // The Score for a guess against an answer.
+    pub fn score(&self, guess: usize, answer: usize) -> Score {
+        self.scorer.score_words(self.guesses[guess], self.answers[answer])
+    }
+
+    // Compute the Cluster Vector for a guess against a subset of answers (see map_to_cv).
+    pub fn cluster_vector(&self, guess: usize, candidates: &[usize]) -> Vec<u32> {
+        let row = self.row(guess);
+        let mut counts = vec![0u32; self.distinct(guess)];
+
+        for answer in candidates {
+            counts[row[*answer] as usize] += 1;
+        }
+
+        let mut cv = Vec::new();
+        for count in counts.into_iter().filter(|count| *count > 0) {
+            while cv.len() < count as usize {
+                cv.push(0);
+            }
+
+            cv[count as usize - 1] += 1;
+        }
+
+        cv
+    }
+
+    // Group candidate answers by the score a guess would get. The guess doesn't need to be in the matrix.
+    pub fn group_by_score(&self, guess: &str, candidates: &[usize]) -> HashMap<Score, Vec<usize>> {
+        let mut map: HashMap<Score, Vec<usize>> = HashMap::new();
+
+        match self.guess_index(guess) {
+            Some(g) => {
+                let row = self.row(g);
+                let mut groups: HashMap<u16, Vec<usize>> = HashMap::new();
+                for answer in candidates {
+                    groups.entry(row[*answer]).or_default().push(*answer);
+                }
+
+                for (_, group) in groups {
+                    map.insert(self.score(g, group[0]), group);
+                }
+            }
+            None => {
+                for answer in candidates {
+                    map.entry(self.scorer.score_words(guess, self.answers[*answer])).or_default().push(*answer);
+                }
+            }
+        }
+
+        map
+    }
+}
+
+// Score one guess against every answer, writing the id of each score into the row and returning the distinct score count.
+fn score_row(scorer: &Scorer, guess: &str, answers: &[&str], row: &mut [u16]) -> u32 {
+    let mut seen: HashMap<Score, u16> = HashMap::new();
+
+    for (cell, answer) in row.iter_mut().zip(answers.iter()) {
+        let next_id = seen.len() as u16;
+        *cell = *seen.entry(scorer.score_words(guess, answer)).or_insert(next_id);
+    }
+
+    seen.len() as u32
+}
+
+// Identify the scorer and word lists a cache was built from (64-bit FNV-1a hash).
+fn fingerprint(scorer: &Scorer, guesses: &[&str], answers: &[&str]) -> u64 {
+    let mut hash = 0xcbf29ce484222325u64;
+    let mut add = |bytes: &[u8]| {
+        for byte in bytes {
+            hash ^= *byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    };
+
+    for row in scorer.table().iter() {
+        add(row);
+    }
+
+    for list in [guesses, answers] {
+        add(b"|");
+        for word in list {
+            add(word.as_bytes());
+            add(b"\n");
+        }
+    }
+
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use crate::{answers::ANSWERS, distance::Manhattan, keyboard::QWERTY};
+    use super::*;
+
+    #[test]
+    fn matrix_matches_word_distance() {
+        let guesses = ["papal", "salal", "qwert"];
+        let matrix = ScoreMatrix::build(Scorer::qwertle(), &guesses, ANSWERS).unwrap();
+
+        for (g, guess) in guesses.iter().enumerate() {
+            let map = Scorer::qwertle().score_map(guess, ANSWERS);
+            assert_eq!(map.len(), matrix.distinct(g));
+
+            let all = (0..ANSWERS.len()).collect::<Vec<_>>();
+            let mut expected = crate::map_to_cv(&map);
+            let mut actual = matrix.cluster_vector(g, &all);
+            expected.sort();
+            actual.sort();
+            assert_eq!(expected, actual);
+
+            // Equal ids mean equal scores
+            let row = matrix.row(g);
+            for a in (0..ANSWERS.len()).step_by(97) {
+                for b in (0..ANSWERS.len()).step_by(89) {
+                    assert_eq!(row[a] == row[b], matrix.score(g, a) == matrix.score(g, b));
+                }
+            }
+        }
+
+        assert_eq!(Some(1), matrix.guess_index("salal"));
+        assert_eq!(Some(2315 - 1), matrix.answer_index("zonal"));
+        assert_eq!(None, matrix.guess_index("zonal"));
+    }
+
+    #[test]
+    fn group_by_score() {
+        let guesses = ["papal"];
+        let matrix = ScoreMatrix::build(Scorer::qwertle(), &guesses, ANSWERS).unwrap();
+        let all = (0..ANSWERS.len()).collect::<Vec<_>>();
+
+        // Guesses in the matrix and not in it give the same groups
+        let from_matrix = matrix.group_by_score("papal", &all);
+        let computed = matrix.group_by_score("papaw", &all);
+        assert_eq!(2118, from_matrix.len());
+        assert_eq!(Scorer::qwertle().score_map("papaw", ANSWERS).len(), computed.len());
+
+        let group = &from_matrix[&"50365".parse::<Score>().unwrap()];
+        let words = group.iter().map(|a| ANSWERS[*a]).collect::<Vec<_>>();
+        assert_eq!(vec!["gamut", "gaunt", "taunt", "vaunt"], words);
+    }
+
+    #[test]
+    fn build_errors() {
+        assert!(matches!(ScoreMatrix::build(Scorer::qwertle(), &["papal"], &["paint", "pains!"]), Err(QwertleError::InvalidLetter { .. })));
+        assert!(matches!(ScoreMatrix::build(Scorer::qwertle(), &["papers"], &["paint"]), Err(QwertleError::WrongLength { .. })));
+
+        // Score ids are u16s, so too many answers is an error rather than a crash
+        let answers = vec!["aa"; u16::MAX as usize + 1];
+        assert!(matches!(ScoreMatrix::build(Scorer::qwertle(), &["aa"], &answers), Err(QwertleError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn cache_round_trip() {
+        let guesses = ["papal", "apple", "qwert"];
+        let answers = &ANSWERS[0..200];
+        let path = std::env::temp_dir().join(format!("qwertle-matrix-test-{}.bin", std::process::id()));
+
+        let matrix = ScoreMatrix::load_or_build(&path, Scorer::qwertle(), &guesses, answers).unwrap();
+        let loaded = ScoreMatrix::load(&path, Scorer::qwertle(), &guesses, answers).unwrap();
+        assert_eq!(matrix.ids, loaded.ids);
+        assert_eq!(matrix.distinct, loaded.distinct);
+
+        // A cache for other words or scoring isn't used
+        assert!(ScoreMatrix::load(&path, Scorer::qwertle(), &guesses[0..2], answers).is_err());
+        assert!(ScoreMatrix::load(&path, Scorer::qwertle(), &guesses, &ANSWERS[1..201]).is_err());
+
+        let manhattan = Scorer::new(QWERTY, Arc::new(Manhattan::default())).unwrap();
+        assert!(ScoreMatrix::load(&path, &manhattan, &guesses, answers).is_err());
+
+        fs::write(&path, b"nonsense").unwrap();
+        assert!(ScoreMatrix::load(&path, Scorer::qwertle(), &guesses, answers).is_err());
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,270 @@
+use serde::Serialize;
+use crate::{matrix::ScoreMatrix, recommend::*};
+
+// A fixed sequence of opening guesses, and how well their scores together split the answers.
+//  worst_case: The most answers which could share all of the scores. One means every answer is identified.
+//  distinct:   How many different combinations of scores the answers get.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Opener<'a> {
+    pub guesses: Vec<&'a str>,
+    pub worst_case: usize,
+    pub distinct: usize,
+}
+
+// The guesses to try early in an opener: those with the smallest worst case on their own, best first.
+pub fn opener_pool(matrix: &ScoreMatrix, size: usize) -> Vec<usize> {
+    let answers = (0..matrix.answers().len()).collect::<Vec<_>>();
+
+    rank_guesses(matrix, &answers, RankBy::WorstCase)
+        .iter()
+        .take(size)
+        .filter_map(|rating| matrix.guess_index(rating.guess))
+        .collect()
+}
+
+// Find the sequences of 'length' guesses whose scores together leave the smallest worst case, keeping up to 'keep'
+// of them with the most distinct score combinations. Every guess but the last comes from the pool; the last can be any guess.
+// The search is branch and bound: each guess is abandoned as soon as it leaves a group larger than the best found so far.
+// 'progress' is called after each guess in the pool has been tried first, with the count done, the total, and the best so far.
+pub fn find_openers<'a>(matrix: &ScoreMatrix<'a>, length: usize, pool: &[usize], keep: usize, progress: &mut dyn FnMut(usize, usize, Option<&Opener<'a>>)) -> Vec<Opener<'a>> {
+    let mut pool_position = vec![None; matrix.guesses().len()];
+    for (position, guess) in pool.iter().enumerate() {
+        pool_position[*guess] = Some(position);
+    }
+
+    let largest_row = (0..matrix.guesses().len()).map(|g| matrix.distinct(g)).max().unwrap_or(0);
+    let mut search = Search {
+        matrix,
+        pool,
+        pool_position,
+        length,
+        keep: keep.max(1),
+        counts: vec![0; largest_row],
+        results: Vec::new(),
+    };
+
+    let everything = vec![(0..matrix.answers().len()).collect::<Vec<_>>()];
+    if length <= 1 {
+        search.try_last(&everything, &mut Vec::new(), None);
+        return search.results;
+    }
+
+    for (position, first) in pool.iter().enumerate() {
+        if search.is_done() {
+            break;
+        }
+
+        let groups = split(matrix, &everything, *first);
+        search.search_from(&groups, &mut vec![*first], position);
+        progress(position + 1, pool.len(), search.results.first());
+    }
+
+    search.results
+}
+
+struct Search<'m, 'a> {
+    matrix: &'m ScoreMatrix<'a>,
+    pool: &'m [usize],
+    pool_position: Vec<Option<usize>>,
+    length: usize,
+    keep: usize,
+
+    // Scratch space counting answers per score id while splitting a group.
+    counts: Vec<u32>,
+
+    // The best openers found so far, all with the same worst case, most distinct first.
+    results: Vec<Opener<'a>>,
+}
+
+impl<'m, 'a> Search<'m, 'a> {
+    // Nothing can beat identifying every answer, so stop once enough perfect openers are found.
+    fn is_done(&self) -> bool {
+        self.results.len() >= self.keep && self.results[0].worst_case <= 1
+    }
+
+    fn bound(&self) -> usize {
+        self.results.first().map_or(usize::MAX, |best| best.worst_case)
+    }
+
+    // Extend the guesses chosen so far (the last of them at 'position' in the pool) with later pool guesses.
+    fn search_from(&mut self, groups: &[Vec<usize>], chosen: &mut Vec<usize>, position: usize) {
+        if chosen.len() + 1 == self.length {
+            self.try_last(groups, chosen, Some(position));
+            return;
+        }
+
+        for next in position + 1..self.pool.len() {
+            if self.is_done() {
+                return;
+            }
+
+            let guess = self.pool[next];
+            let split = split(self.matrix, groups, guess);
+
+            chosen.push(guess);
+            self.search_from(&split, chosen, next);
+            chosen.pop();
+        }
+    }
+
+    // Try every guess as the last one. Guesses in the pool at or before 'position' were already tried earlier in the sequence.
+    fn try_last(&mut self, groups: &[Vec<usize>], chosen: &mut Vec<usize>, position: Option<usize>) {
+        for guess in 0..self.matrix.guesses().len() {
+            if position.is_some_and(|position| self.pool_position[guess].is_some_and(|p| p <= position)) {
+                continue;
+            }
+
+            if let Some((worst_case, distinct)) = self.split_sizes(groups, guess, self.bound()) {
+                chosen.push(guess);
+                self.record(chosen, worst_case, distinct);
+                chosen.pop();
+
+                if self.is_done() {
+                    return;
+                }
+            }
+        }
+    }
+
+    fn record(&mut self, chosen: &[usize], worst_case: usize, distinct: usize) {
+        if worst_case < self.bound() {
+            self.results.clear();
+        }
+
+        let opener = Opener { guesses: chosen.iter().map(|g| self.matrix.guesses()[*g]).collect(), worst_case, distinct };
+        let at = self.results.partition_point(|other| other.distinct >= distinct);
+        if at < self.keep {
+            self.results.insert(at, opener);
+            self.results.truncate(self.keep);
+        }
+    }
+
+    // The largest group and the number of groups after splitting each group by a guess's scores,
+    // or None as soon as a group larger than 'bound' is left. Groups are largest first, so most guesses fail quickly.
+    fn split_sizes(&mut self, groups: &[Vec<usize>], guess: usize, bound: usize) -> Option<(usize, usize)> {
+        let row = self.matrix.row(guess);
+        let mut worst_case = 0;
+        let mut distinct = 0;
+
+        for (i, group) in groups.iter().enumerate() {
+            // Only single answers are left, and they can't be split further
+            if group.len() == 1 {
+                return Some((worst_case.max(1), distinct + groups.len() - i));
+            }
+
+            for answer in group {
+                let count = &mut self.counts[row[*answer] as usize];
+                if *count == 0 {
+                    distinct += 1;
+                }
+
+                *count += 1;
+                worst_case = worst_case.max(*count as usize);
+            }
+
+            for answer in group {
+                self.counts[row[*answer] as usize] = 0;
+            }
+
+            if worst_case > bound {
+                return None;
+            }
+        }
+
+        Some((worst_case, distinct))
+    }
+}
+
+// Split each group of answers by the score a guess gets, returning the new groups largest first.
+fn split(matrix: &ScoreMatrix, groups: &[Vec<usize>], guess: usize) -> Vec<Vec<usize>> {
+    let row = matrix.row(guess);
+    let mut result = Vec::new();
+    let mut slots = vec![usize::MAX; matrix.distinct(guess)];
+
+    for group in groups {
+        let start = result.len();
+
+        for answer in group {
+            let id = row[*answer] as usize;
+            if slots[id] == usize::MAX {
+                slots[id] = result.len();
+                result.push(Vec::new());
+            }
+
+            result[slots[id]].push(*answer);
+        }
+
+        for new_group in result[start..].iter() {
+            slots[row[new_group[0]] as usize] = usize::MAX;
+        }
+    }
+
+    result.sort_by_key(|group| std::cmp::Reverse(group.len()));
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use crate::{*, answers::ANSWERS, distance::Scorer};
+    use super::*;
+
+    // Check an opener's worst case and distinct count by scoring every answer directly.
+    fn verify(opener: &Opener) {
+        let mut groups: HashMap<Vec<Score>, usize> = HashMap::new();
+        for answer in ANSWERS {
+            *groups.entry(opener.guesses.iter().map(|guess| word_distance(guess, answer).unwrap()).collect()).or_default() += 1;
+        }
+
+        assert_eq!(groups.len(), opener.distinct);
+        assert_eq!(*groups.values().max().unwrap(), opener.worst_case);
+    }
+
+    #[test]
+    fn single_guesses() {
+        let matrix = ScoreMatrix::build(Scorer::qwertle(), ANSWERS, ANSWERS).unwrap();
+        let pool = opener_pool(&matrix, 5);
+        assert_eq!(5, pool.len());
+
+        let openers = find_openers(&matrix, 1, &pool, 3, &mut |_, _, _| {});
+        assert_eq!(3, openers.len());
+        assert!(openers.windows(2).all(|pair| pair[0].worst_case == pair[1].worst_case && pair[0].distinct >= pair[1].distinct));
+
+        let best = rate_matrix_guess(&matrix, pool[0], &(0..ANSWERS.len()).collect::<Vec<_>>(), true);
+        assert_eq!(best.worst_case, openers[0].worst_case);
+        openers.iter().for_each(verify);
+    }
+
+    #[test]
+    fn guess_pairs() {
+        let matrix = ScoreMatrix::build(Scorer::qwertle(), ANSWERS, ANSWERS).unwrap();
+        let pool = opener_pool(&matrix, 10);
+
+        let mut calls = Vec::new();
+        let openers = find_openers(&matrix, 2, &pool, 5, &mut |done, total, best| calls.push((done, total, best.map(|b| b.worst_case))));
+        assert!(!openers.is_empty() && openers.len() <= 5);
+        assert!(openers.iter().all(|opener| opener.guesses.len() == 2 && opener.guesses[0] != opener.guesses[1]));
+        openers.iter().for_each(verify);
+
+        // A second guess splits the groups the first leaves
+        let single = find_openers(&matrix, 1, &pool, 1, &mut |_, _, _| {});
+        assert!(openers[0].worst_case < single[0].worst_case);
+
+        // Progress is reported after each first guess, unless the search finishes early
+        assert!(!calls.is_empty() && calls.len() <= pool.len());
+        assert_eq!((1, pool.len()), (calls[0].0, calls[0].1));
+        assert_eq!(Some(openers[0].worst_case), calls.last().unwrap().2);
+    }
+
+    #[test]
+    fn guess_triples() {
+        let matrix = ScoreMatrix::build(Scorer::qwertle(), ANSWERS, ANSWERS).unwrap();
+        let pool = opener_pool(&matrix, 4);
+
+        let openers = find_openers(&matrix, 3, &pool, 2, &mut |_, _, _| {});
+        assert_eq!(1, openers[0].worst_case);
+        assert_eq!(ANSWERS.len(), openers[0].distinct);
+        assert!(openers.iter().all(|opener| opener.guesses.len() == 3));
+        openers.iter().for_each(verify);
+    }
+}
@@ -0,0 +1,647 @@
+use std::collections::HashMap;
+use serde::Serialize;
+use crate::{*, boards::MultiSolver, colors::Tile, distance::Scorer, fuzzy::*, history::*, letter_matrix::LetterMatrix, matrix::ScoreMatrix, openers::Opener, priors::Priors, recommend::GuessRating, report::OpenerStats, simulate::*, solver::Solver, tree::TreeStats};
+
+// The results of each CLI mode as serializable structs, and the code to write them as JSON, CSV, or Markdown.
+// Text output is written by each mode itself; CSV and Markdown show the main table of each result.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+    Markdown,
+}
+
+impl Format {
+    pub fn parse(name: &str) -> Option<Format> {
+        match name.to_ascii_lowercase().as_str() {
+            "text" | "txt" => Some(Format::Text),
+            "json" => Some(Format::Json),
+            "csv" => Some(Format::Csv),
+            "md" | "markdown" => Some(Format::Markdown),
+            _ => None,
+        }
+    }
+}
+
+// Results which can be shown as a table: the column names, then the cells for each row.
+pub trait Table {
+    fn columns(&self) -> Vec<&'static str>;
+    fn rows(&self) -> Vec<Vec<String>>;
+}
+
+// Write results in a structured format, or return None for Text.
+pub fn render<T: Serialize + Table>(results: &T, format: Format) -> Option<String> {
+    match format {
+        Format::Text => None,
+        Format::Json => Some(to_json(results)),
+        Format::Csv => Some(to_csv(results)),
+        Format::Markdown => Some(to_markdown(results)),
+    }
+}
+
+pub fn to_json<T: Serialize>(results: &T) -> String {
+    serde_json::to_string_pretty(results).unwrap() + "\n"
+}
+
+// Write a table as CSV, quoting cells which contain commas, quotes, or line breaks.
+pub fn to_csv(table: &dyn Table) -> String {
+    let cell = |text: &str| {
+        if text.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", text.replace('"', "\"\""))
+        } else {
+            text.to_string()
+        }
+    };
+
+    let mut text = table.columns().join(",") + "\n";
+    for row in table.rows() {
+        text += &row.iter().map(|c| cell(c)).collect::<Vec<_>>().join(",");
+        text += "\n";
+    }
+
+    text
+}
+
+// Write a table as a Markdown table, escaping '|' in cells.
+pub fn to_markdown(table: &dyn Table) -> String {
+    let columns = table.columns();
+    let mut text = format!("| {} |\n", columns.join(" | "));
+    text += &format!("|{}\n", "---|".repeat(columns.len()));
+
+    for row in table.rows() {
+        let cells = row.iter().map(|c| c.replace('|', "\\|").replace('\n', " ")).collect::<Vec<_>>();
+        text += &format!("| {} |\n", cells.join(" | "));
+    }
+
+    text
+}
+
+// best: how many distinct scores each guess can get across the answers.
+#[derive(Debug, Clone, Serialize)]
+pub struct GuessDistinct<'a> {
+    pub guess: &'a str,
+    pub distinct: usize,
+}
+
+pub fn guess_distinct_counts<'a>(matrix: &ScoreMatrix<'a>) -> Vec<GuessDistinct<'a>> {
+    matrix.guesses().iter().enumerate().map(|(g, guess)| GuessDistinct { guess, distinct: matrix.distinct(g) }).collect()
+}
+
+impl Table for Vec<GuessDistinct<'_>> {
+    fn columns(&self) -> Vec<&'static str> {
+        vec!["guess", "distinct"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.iter().map(|r| vec![r.guess.to_string(), r.distinct.to_string()]).collect()
+    }
+}
+
+// report: every opener's measures, with the letter coverage of each position in its own column so the CSV sorts by any of them.
+const COVERAGE_COLUMNS: [&str; 16] = [
+    "coverage_1", "coverage_2", "coverage_3", "coverage_4", "coverage_5", "coverage_6", "coverage_7", "coverage_8",
+    "coverage_9", "coverage_10", "coverage_11", "coverage_12", "coverage_13", "coverage_14", "coverage_15", "coverage_16",
+];
+
+impl Table for Vec<OpenerStats<'_>> {
+    fn columns(&self) -> Vec<&'static str> {
+        let positions = self.iter().map(|s| s.coverage.len()).max().unwrap_or(0).min(COVERAGE_COLUMNS.len());
+
+        let mut columns = vec!["guess", "is_answer", "distinct", "entropy", "expected_size", "worst_case"];
+        columns.extend_from_slice(&COVERAGE_COLUMNS[..positions]);
+        columns
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        let positions = self.columns().len() - 6;
+
+        self.iter()
+            .map(|s| {
+                let mut row = vec![s.guess.to_string(), s.is_answer.to_string(), s.distinct.to_string(), format!("{:.4}", s.entropy), format!("{:.4}", s.expected_size), s.worst_case.to_string()];
+                row.extend((0..positions).map(|pos| s.coverage.get(pos).map_or(String::new(), |c| format!("{c:.4}"))));
+                row
+            })
+            .collect()
+    }
+}
+
+// word: the score for each answer, the cluster vector, and the letters at each distance from each guess letter.
+#[derive(Debug, Clone, Serialize)]
+pub struct WordReport<'a> {
+    pub guess: String,
+    pub allowed: bool,
+    pub distinct: usize,
+    pub cv: Vec<u32>,
+    pub answers: Vec<AnswerScore<'a>>,
+    pub letter_table: Vec<LetterTableRow>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AnswerScore<'a> {
+    pub score: Score,
+    pub answer: &'a str,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LetterTableRow {
+    pub letter: char,
+    pub position: usize,
+
+    // Entry D[i] is the letters at distance i, most common in this position first.
+    pub by_distance: Vec<String>,
+}
+
+// Build the word report, with answers in increasing score order.
+pub fn word_report<'a>(scorer: &Scorer, guess: &str, answers: &[&'a str], allowed: bool) -> WordReport<'a> {
+    let map = scorer.score_map(guess, answers);
+    let cv = map_to_cv(&map);
+
+    let mut scores = map.keys().copied().collect::<Vec<_>>();
+    scores.sort();
+
+    let answer_scores = scores.iter()
+        .flat_map(|score| map[score].iter().map(|answer| AnswerScore { score: *score, answer }))
+        .collect();
+
+    WordReport {
+        guess: guess.to_string(),
+        allowed,
+        distinct: map.len(),
+        cv,
+        answers: answer_scores,
+        letter_table: letter_table_rows(scorer, guess, &letter_frequencies(answers)),
+    }
+}
+
+// The letters at each distance (0 up to the scorer's largest distance) from each guess letter, as in letter_table.
+pub fn letter_table_rows(scorer: &Scorer, guess: &str, frequencies: &HashMap<(char, u8), u16>) -> Vec<LetterTableRow> {
+    guess.chars().enumerate()
+        .map(|(position, letter)| LetterTableRow {
+            letter,
+            position,
+            by_distance: (0..=scorer.max_distance()).map(|distance| letters(scorer.letters_at_distance(letter, position as u8, distance, frequencies))).collect(),
+        })
+        .collect()
+}
+
+impl Table for WordReport<'_> {
+    fn columns(&self) -> Vec<&'static str> {
+        vec!["score", "answer"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.answers.iter().map(|r| vec![r.score.to_string(), r.answer.to_string()]).collect()
+    }
+}
+
+// letter_options: the letters each guess letter could be, given its score digit.
+#[derive(Debug, Clone, Serialize)]
+pub struct LetterOptionsReport {
+    pub guess: String,
+    pub score: Score,
+    pub positions: Vec<LetterOption>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LetterOption {
+    pub letter: char,
+    pub distance: u8,
+
+    // Letters at the distance, most common in this position first.
+    pub options: String,
+}
+
+pub fn letter_options_report(scorer: &Scorer, guess: &str, score: &Score, frequencies: &HashMap<(char, u8), u16>) -> LetterOptionsReport {
+    // If fewer letters than score digits were passed, score them against the last score digits
+    let digits = score.last(guess.len());
+
+    let positions = guess.chars().zip(digits.digits().iter()).enumerate()
+        .map(|(position, (letter, distance))| LetterOption {
+            letter,
+            distance: *distance,
+            options: letters(scorer.letters_at_distance(letter, position as u8, *distance, frequencies)),
+        })
+        .collect();
+
+    LetterOptionsReport { guess: guess.to_string(), score: *score, positions }
+}
+
+impl Table for LetterOptionsReport {
+    fn columns(&self) -> Vec<&'static str> {
+        vec!["position", "letter", "distance", "options"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.positions.iter().enumerate().map(|(i, r)| vec![(i + 1).to_string(), r.letter.to_string(), r.distance.to_string(), r.options.clone()]).collect()
+    }
+}
+
+// answer_options: the answers with scores closest to the score seen.
+#[derive(Debug, Clone, Serialize)]
+pub struct AnswerOptionsReport<'a> {
+    pub guess: String,
+    pub score: Score,
+    pub within: u32,
+    pub answers: Vec<AnswerOption<'a>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AnswerOption<'a> {
+    pub answer: &'a str,
+    pub score: Score,
+    pub distance: u32,
+}
+
+// Answers equally close to the score are listed most likely first.
+pub fn answer_options_report<'a>(scorer: &Scorer, guess: &str, score: &Score, answers: &[&'a str], within: u32, priors: &Priors) -> AnswerOptionsReport<'a> {
+    let mut options = scorer.closest_answers(guess, score, answers, within);
+    options.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| priors.weight(b.1).total_cmp(&priors.weight(a.1))).then_with(|| a.1.cmp(b.1)));
+
+    let options = options
+        .into_iter()
+        .map(|(distance, answer, score)| AnswerOption { answer, score, distance })
+        .collect();
+
+    AnswerOptionsReport { guess: guess.to_string(), score: *score, within, answers: options }
+}
+
+impl Table for AnswerOptionsReport<'_> {
+    fn columns(&self) -> Vec<&'static str> {
+        vec!["answer", "score", "distance"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.answers.iter().map(|r| vec![r.answer.to_string(), r.score.to_string(), r.distance.to_string()]).collect()
+    }
+}
+
+// letters: the letters each position could be after every turn, one row per letter.
+impl Table for LetterMatrix {
+    fn columns(&self) -> Vec<&'static str> {
+        vec!["position", "letter", "probability"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.positions.iter().enumerate()
+            .flat_map(|(i, letters)| letters.iter().map(move |chance| vec![(i + 1).to_string(), chance.letter.to_string(), chance.probability.to_string()]))
+            .collect()
+    }
+}
+
+// fuzzy: each answer's probability, with the score it would give each guess.
+#[derive(Debug, Clone, Serialize)]
+pub struct FuzzyMatch<'a> {
+    pub answer: &'a str,
+    pub probability: f64,
+    pub scores: Vec<Score>,
+}
+
+pub fn fuzzy_matches<'a>(scorer: &Scorer, turns: &[(&str, FuzzyScore)], answers: &[&'a str], priors: &Priors) -> Vec<FuzzyMatch<'a>> {
+    rank_candidates(scorer, turns, answers, priors)
+        .into_iter()
+        .map(|(probability, answer)| FuzzyMatch {
+            answer,
+            probability,
+            scores: turns.iter().map(|(guess, _)| scorer.score_words(guess, answer)).collect(),
+        })
+        .collect()
+}
+
+impl Table for Vec<FuzzyMatch<'_>> {
+    fn columns(&self) -> Vec<&'static str> {
+        vec!["answer", "probability", "scores"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.iter().map(|r| vec![r.answer.to_string(), r.probability.to_string(), join(&r.scores)]).collect()
+    }
+}
+
+// solve: the turns so far, the answers left, and the best next guesses.
+#[derive(Debug, Clone, Serialize)]
+pub struct SolveReport<'a> {
+    pub turns: Vec<Turn>,
+    pub candidates: Vec<&'a str>,
+    pub probabilities: Vec<f64>,
+    pub solved: bool,
+    pub recommendation: Option<&'a str>,
+    pub recommendations: Vec<GuessRating<'a>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Turn {
+    pub guess: String,
+    pub score: Score,
+}
+
+// Build the solve report, with up to 'limit' recommendations. Once two or fewer answers remain, guess one of them.
+pub fn solve_report<'a>(solver: &Solver<'a>, limit: usize) -> SolveReport<'a> {
+    let candidates = solver.candidates();
+    let recommendations = if candidates.len() > 2 { solver.recommendations().into_iter().take(limit).collect() } else { Vec::new() };
+    let recommendation = recommendations.first().map(|r: &GuessRating<'a>| r.guess).or(candidates.first().copied());
+
+    SolveReport {
+        turns: solver.history().iter().map(|(guess, score)| Turn { guess: guess.clone(), score: *score }).collect(),
+        solved: solver.is_solved(),
+        probabilities: solver.probabilities(),
+        candidates,
+        recommendation,
+        recommendations,
+    }
+}
+
+impl Table for SolveReport<'_> {
+    fn columns(&self) -> Vec<&'static str> {
+        vec!["guess", "is_candidate", "distinct", "entropy", "expected_size", "worst_case"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        rating_rows(&self.recommendations)
+    }
+}
+
+fn rating_rows(ratings: &[GuessRating]) -> Vec<Vec<String>> {
+    ratings.iter()
+        .map(|r| vec![r.guess.to_string(), r.is_candidate.to_string(), r.distinct.to_string(), format!("{:.4}", r.entropy), format!("{:.4}", r.expected_size), r.worst_case.to_string()])
+        .collect()
+}
+
+// boards: each board's turns and answers left, and the best next guesses across the boards still being played.
+#[derive(Debug, Clone, Serialize)]
+pub struct BoardsReport<'a> {
+    pub boards: Vec<BoardReport<'a>>,
+    pub recommendation: Option<&'a str>,
+    pub recommendations: Vec<GuessRating<'a>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BoardReport<'a> {
+    pub turns: Vec<Turn>,
+    pub candidates: Vec<&'a str>,
+    pub finished: bool,
+}
+
+// Build the boards report, with up to 'limit' recommendations.
+pub fn boards_report<'a>(solver: &MultiSolver<'a>, limit: usize) -> BoardsReport<'a> {
+    let boards = solver.boards().iter().enumerate()
+        .map(|(index, board)| BoardReport {
+            turns: board.history().iter().map(|(guess, score)| Turn { guess: guess.clone(), score: *score }).collect(),
+            candidates: board.candidates(),
+            finished: solver.is_finished(index),
+        })
+        .collect();
+
+    // Once a board's answer is known, it's the next guess, so the ranking isn't needed
+    let recommendations = if solver.is_done() || solver.known_answer().is_some() { Vec::new() } else { solver.recommendations().into_iter().take(limit).collect() };
+    BoardsReport { boards, recommendation: solver.recommend(), recommendations }
+}
+
+impl Table for BoardsReport<'_> {
+    fn columns(&self) -> Vec<&'static str> {
+        vec!["guess", "is_candidate", "distinct", "entropy", "expected_size", "worst_case"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        rating_rows(&self.recommendations)
+    }
+}
+
+// stats: the recorded games replayed through the solver, and totals across them.
+#[derive(Debug, Clone, Serialize)]
+pub struct StatsReport<'a> {
+    pub summary: HistorySummary,
+    pub games: Vec<GameReview<'a>>,
+}
+
+// One row per turn, so the CSV can be filtered to the turns which fell behind the recommended guess.
+impl Table for StatsReport<'_> {
+    fn columns(&self) -> Vec<&'static str> {
+        vec!["date", "answer", "turn", "guess", "score", "candidates_before", "candidates_after", "recommended", "recommended_after"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.games.iter()
+            .flat_map(|game| game.turns.iter().enumerate().map(move |(index, turn)| vec![
+                game.date.map(|date| date.to_string()).unwrap_or_default(),
+                game.answer.clone(),
+                (index + 1).to_string(),
+                turn.guess.clone(),
+                turn.score.to_string(),
+                turn.candidates_before.to_string(),
+                turn.candidates_after.to_string(),
+                turn.recommended.unwrap_or_default().to_string(),
+                turn.recommended_after.map(|after| after.to_string()).unwrap_or_default(),
+            ]))
+            .collect()
+    }
+}
+
+// openers: the fixed opening sequences which best split the answers.
+impl Table for Vec<Opener<'_>> {
+    fn columns(&self) -> Vec<&'static str> {
+        vec!["guesses", "worst_case", "distinct"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.iter().map(|r| vec![r.guesses.join(" "), r.worst_case.to_string(), r.distinct.to_string()]).collect()
+    }
+}
+
+// tree: guess counts for the strategy tree from each opener.
+#[derive(Debug, Clone, Serialize)]
+pub struct TreeSummary {
+    pub opener: String,
+
+    #[serde(flatten)]
+    pub stats: TreeStats,
+}
+
+impl Table for Vec<TreeSummary> {
+    fn columns(&self) -> Vec<&'static str> {
+        vec!["opener", "answers", "max_guesses", "average_guesses", "histogram"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.iter()
+            .map(|r| vec![r.opener.clone(), r.stats.answers.to_string(), r.stats.max_guesses.to_string(), format!("{:.4}", r.stats.average_guesses), join(&r.stats.histogram)])
+            .collect()
+    }
+}
+
+// check: the recorded scores which the layout and metric don't reproduce.
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckReport<'a> {
+    pub layout: String,
+    pub metric: String,
+    pub recorded: usize,
+    pub matching: usize,
+    pub mismatches: Vec<Mismatch<'a>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Mismatch<'a> {
+    pub guess: &'a str,
+    pub answer: &'a str,
+    pub recorded: Score,
+    pub computed: Score,
+}
+
+pub fn check_report<'a>(scorer: &Scorer, recorded: &'a [RecordedScore]) -> CheckReport<'a> {
+    let mismatches = find_mismatches(scorer, recorded)
+        .into_iter()
+        .map(|(r, computed)| Mismatch { guess: &r.guess, answer: &r.answer, recorded: r.score, computed })
+        .collect::<Vec<_>>();
+
+    CheckReport {
+        layout: scorer.layout().name.to_string(),
+        metric: format!("{:?}", scorer.model()),
+        recorded: recorded.len(),
+        matching: recorded.len() - mismatches.len(),
+        mismatches,
+    }
+}
+
+impl Table for CheckReport<'_> {
+    fn columns(&self) -> Vec<&'static str> {
+        vec!["guess", "answer", "recorded", "computed"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.mismatches.iter().map(|r| vec![r.guess.to_string(), r.answer.to_string(), r.recorded.to_string(), r.computed.to_string()]).collect()
+    }
+}
+
+// decode: the score read from tile colors, with the color and digit of each tile.
+#[derive(Debug, Clone, Serialize)]
+pub struct DecodeReport {
+    pub score: Score,
+    pub tiles: Vec<Tile>,
+}
+
+impl Table for DecodeReport {
+    fn columns(&self) -> Vec<&'static str> {
+        vec!["position", "color", "digit"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.tiles.iter().enumerate().map(|(i, tile)| vec![(i + 1).to_string(), tile.color.to_string(), tile.digit.to_string()]).collect()
+    }
+}
+
+// simulate: guess counts, failures, and time for each strategy.
+#[derive(Debug, Clone, Serialize)]
+pub struct SimulationSummary<'a> {
+    pub strategy: String,
+    pub games: usize,
+    pub max_guesses: usize,
+    pub average_guesses: f64,
+    pub histogram: Vec<usize>,
+    pub failures: Vec<&'a str>,
+    pub seconds: f64,
+}
+
+impl<'a> SimulationSummary<'a> {
+    pub fn new(simulation: &Simulation<'a>) -> SimulationSummary<'a> {
+        SimulationSummary {
+            strategy: simulation.strategy.clone(),
+            games: simulation.games.len(),
+            max_guesses: simulation.max_guesses(),
+            average_guesses: simulation.average_guesses(),
+            histogram: simulation.histogram(),
+            failures: simulation.failures().iter().map(|game| game.answer).collect(),
+            seconds: simulation.elapsed.as_secs_f64(),
+        }
+    }
+}
+
+impl Table for Vec<SimulationSummary<'_>> {
+    fn columns(&self) -> Vec<&'static str> {
+        vec!["strategy", "games", "max_guesses", "average_guesses", "failures", "seconds", "histogram"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.iter()
+            .map(|r| vec![r.strategy.clone(), r.games.to_string(), r.max_guesses.to_string(), format!("{:.4}", r.average_guesses), r.failures.len().to_string(), format!("{:.3}", r.seconds), join(&r.histogram)])
+            .collect()
+    }
+}
+
+fn letters(options: Vec<(u16, char)>) -> String {
+    options.iter().map(|(_, letter)| *letter).collect()
+}
+
+// Join values with spaces, for a single table cell.
+fn join<T: ToString>(values: &[T]) -> String {
+    values.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answers::ANSWERS;
+    use super::*;
+
+    #[test]
+    fn formats() {
+        assert_eq!(Some(Format::Markdown), Format::parse("MD"));
+        assert_eq!(Some(Format::Csv), Format::parse("csv"));
+        assert_eq!(None, Format::parse("xml"));
+    }
+
+    #[test]
+    fn answer_options_formats() {
+        let score = "50365".parse::<Score>().unwrap();
+        let report = answer_options_report(Scorer::qwertle(), "papal", &score, ANSWERS, 0, &Priors::uniform());
+        assert_eq!(None, render(&report, Format::Text));
+
+        let csv = render(&report, Format::Csv).unwrap();
+        assert_eq!("answer,score,distance\ngamut,50365,0\ngaunt,50365,0\ntaunt,50365,0\nvaunt,50365,0\n", csv);
+
+        let markdown = render(&report, Format::Markdown).unwrap();
+        assert!(markdown.starts_with("| answer | score | distance |\n|---|---|---|\n| gamut | 50365 | 0 |\n"));
+
+        let json: serde_json::Value = serde_json::from_str(&render(&report, Format::Json).unwrap()).unwrap();
+        assert_eq!("50365", json["score"]);
+        assert_eq!("gaunt", json["answers"][1]["answer"]);
+
+        // More likely answers come first
+        let priors = Priors::parse("taunt 50\ngaunt 5\ngamut 1\n").unwrap();
+        let report = answer_options_report(Scorer::qwertle(), "papal", &score, ANSWERS, 0, &priors);
+        let answers = report.answers.iter().map(|option| option.answer).collect::<Vec<_>>();
+        assert_eq!(vec!["taunt", "gaunt", "gamut", "vaunt"], answers);
+    }
+
+    #[test]
+    fn csv_quoting() {
+        struct Cells;
+        impl Table for Cells {
+            fn columns(&self) -> Vec<&'static str> {
+                vec!["a", "b"]
+            }
+
+            fn rows(&self) -> Vec<Vec<String>> {
+                vec![vec!["x,y".to_string(), "say \"hi\"".to_string()], vec!["a|b".to_string(), "plain".to_string()]]
+            }
+        }
+
+        assert_eq!("a,b\n\"x,y\",\"say \"\"hi\"\"\"\na|b,plain\n", to_csv(&Cells));
+        assert_eq!("| a | b |\n|---|---|\n| x,y | say \"hi\" |\n| a\\|b | plain |\n", to_markdown(&Cells));
+    }
+
+    #[test]
+    fn word_and_letter_reports() {
+        let report = word_report(Scorer::qwertle(), "papal", ANSWERS, true);
+        assert_eq!(2118, report.distinct);
+        assert_eq!(ANSWERS.len(), report.answers.len());
+        assert!(report.answers.windows(2).all(|w| w[0].score <= w[1].score));
+        assert_eq!(5, report.letter_table.len());
+        assert_eq!("p", report.letter_table[0].by_distance[0]);
+
+        let score = "32521".parse::<Score>().unwrap();
+        let report = letter_options_report(Scorer::qwertle(), "apple", &score, &letter_frequencies(ANSWERS));
+        assert_eq!(5, report.positions.len());
+        assert_eq!(3, report.positions[0].distance);
+        assert!(report.positions[0].options.contains('r'));
+    }
+}
@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+use crate::error::QwertleError;
+
+// How likely each answer is to be chosen, relative to the others. Every answer is equally likely unless
+// weights are loaded from a word frequency file. Words missing from the file get the smallest weight it lists,
+// so unusual answers are ranked last but stay possible.
+#[derive(Debug, Clone)]
+pub struct Priors {
+    weights: HashMap<String, f64>,
+    missing: f64,
+}
+
+impl Priors {
+    pub fn uniform() -> Priors {
+        Priors { weights: HashMap::new(), missing: 1.0 }
+    }
+
+    // Parse word frequencies, one 'word count' (or 'word,count') per line. Counts may be any positive number.
+    // Blank lines and lines starting with '#' are skipped.
+    pub fn parse(text: &str) -> Result<Priors, QwertleError> {
+        let mut weights = HashMap::new();
+
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let parts = line.split(|c: char| c == ',' || c.is_whitespace()).filter(|part| !part.is_empty()).collect::<Vec<_>>();
+            let count = parts.get(1).and_then(|count| count.parse::<f64>().ok());
+
+            match (parts.len(), count) {
+                (2, Some(count)) if count > 0.0 && count.is_finite() => {
+                    weights.insert(parts[0].to_ascii_lowercase(), count);
+                }
+                _ => return Err(QwertleError::InvalidInput(format!("Line {} should be 'word count' with a positive count, not '{line}'.", number + 1))),
+            }
+        }
+
+        if weights.is_empty() {
+            return Err(QwertleError::InvalidInput("The word frequency file has no words.".to_string()));
+        }
+
+        let missing = weights.values().copied().fold(f64::INFINITY, f64::min);
+        Ok(Priors { weights, missing })
+    }
+
+    // Whether every word has the same weight.
+    pub fn is_uniform(&self) -> bool {
+        self.weights.is_empty()
+    }
+
+    pub fn weight(&self, word: &str) -> f64 {
+        self.weights.get(&word.to_ascii_lowercase()).copied().unwrap_or(self.missing)
+    }
+
+    // The weight of each word, in the same order.
+    pub fn weights(&self, words: &[&str]) -> Vec<f64> {
+        words.iter().map(|word| self.weight(word)).collect()
+    }
+
+    // The chance of each word being the answer, if the answer is one of them.
+    pub fn probabilities(&self, words: &[&str]) -> Vec<f64> {
+        let weights = self.weights(words);
+        let total = weights.iter().sum::<f64>();
+        weights.iter().map(|weight| if total > 0.0 { weight / total } else { 0.0 }).collect()
+    }
+
+    // Like letter_frequencies, but each answer counts by its weight, scaled so the average answer counts once.
+    pub fn letter_frequencies(&self, answers: &[&str]) -> HashMap<(char, u8), u16> {
+        let mut totals: HashMap<(char, u8), f64> = HashMap::new();
+
+        for (answer, probability) in answers.iter().zip(self.probabilities(answers)) {
+            for (pos, letter) in answer.chars().enumerate() {
+                *totals.entry((letter, pos as u8)).or_insert(0.0) += probability * answers.len() as f64;
+            }
+        }
+
+        totals.into_iter().map(|(key, total)| (key, total.round().min(u16::MAX as f64) as u16)).collect()
+    }
+}
+
+impl Default for Priors {
+    fn default() -> Self {
+        Priors::uniform()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{*, answers::ANSWERS};
+    use super::*;
+
+    #[test]
+    fn parse_priors() {
+        let priors = Priors::parse("# word count\nabout 900\nAPPLE,30\n\nvivid 2.5\n").unwrap();
+        assert!(!priors.is_uniform());
+        assert_eq!(900.0, priors.weight("about"));
+        assert_eq!(30.0, priors.weight("apple"));
+
+        // Words not listed get the smallest weight
+        assert_eq!(2.5, priors.weight("rigid"));
+        assert_eq!(vec![900.0, 2.5], priors.weights(&["about", "zebra"]));
+
+        assert!(Priors::parse("").is_err());
+        assert!(Priors::parse("apple").is_err());
+        assert!(Priors::parse("apple -3").is_err());
+        assert!(Priors::parse("apple 3 4").is_err());
+    }
+
+    #[test]
+    fn uniform_priors() {
+        let priors = Priors::uniform();
+        assert!(priors.is_uniform());
+        assert_eq!(vec![0.25; 4], priors.probabilities(&["gamut", "gaunt", "taunt", "vaunt"]));
+        assert_eq!(letter_frequencies(ANSWERS), priors.letter_frequencies(ANSWERS));
+    }
+
+    #[test]
+    fn weighted_letter_frequencies() {
+        let priors = Priors::parse("gamut 1\ntaunt 3\n").unwrap();
+        let probabilities = priors.probabilities(&["gamut", "taunt"]);
+        assert_eq!(vec![0.25, 0.75], probabilities);
+
+        // 'taunt' counts three times as much as 'gamut', and the two together still count twice
+        let frequencies = priors.letter_frequencies(&["gamut", "taunt"]);
+        assert_eq!(Some(&2), frequencies.get(&('a', 1)));
+        assert_eq!(Some(&1), frequencies.get(&('g', 0)));
+        assert_eq!(Some(&2), frequencies.get(&('t', 0)));
+    }
+}
@@ -0,0 +1,284 @@
+use std::cmp::Ordering;
+use serde::Serialize;
+use crate::{*, distance::Scorer, matrix::ScoreMatrix};
+
+// How well one guess splits a set of candidate answers.
+//  distinct:      How many different scores the guess could get.
+//  entropy:       Expected information from the score, in bits. Higher is better.
+//  expected_size: Expected number of candidates left after the score. Lower is better.
+//  worst_case:    Largest number of candidates which could be left after the score. Lower is better.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GuessRating<'a> {
+    pub guess: &'a str,
+    pub is_candidate: bool,
+    pub distinct: usize,
+    pub entropy: f64,
+    pub expected_size: f64,
+    pub worst_case: usize,
+}
+
+// Which measure to rank guesses by first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RankBy {
+    Entropy,
+    ExpectedSize,
+    WorstCase,
+}
+
+impl RankBy {
+    pub fn parse(name: &str) -> Option<RankBy> {
+        match name.to_ascii_lowercase().as_str() {
+            "entropy" => Some(RankBy::Entropy),
+            "expected" | "expected_size" => Some(RankBy::ExpectedSize),
+            "worst" | "worst_case" | "minimax" => Some(RankBy::WorstCase),
+            _ => None,
+        }
+    }
+}
+
+// Shannon entropy (in bits) of the scores for a guess, from the Cluster Vector of its answer map.
+// Each group of size s out of n answers occurs with probability s/n.
+pub fn cv_entropy(cv: &[u32]) -> f64 {
+    let total = cv_total(cv) as f64;
+    let mut entropy = 0.0;
+
+    for (i, count) in cv.iter().enumerate() {
+        let p = (i + 1) as f64 / total;
+        entropy -= *count as f64 * p * p.log2();
+    }
+
+    entropy
+}
+
+// Expected number of answers left after the score, from the Cluster Vector.
+// An answer lands in a group of size s with probability s/n, so this is sum(s^2) / n.
+pub fn cv_expected_size(cv: &[u32]) -> f64 {
+    let total = cv_total(cv) as f64;
+    let mut sum = 0.0;
+
+    for (i, count) in cv.iter().enumerate() {
+        let size = (i + 1) as f64;
+        sum += *count as f64 * size * size;
+    }
+
+    if total > 0.0 { sum / total } else { 0.0 }
+}
+
+// Largest group size in the Cluster Vector (the length of the vector, as the last entry is non-zero).
+pub fn cv_worst_case(cv: &[u32]) -> usize {
+    cv.len()
+}
+
+// Total number of answers covered by a Cluster Vector.
+fn cv_total(cv: &[u32]) -> u32 {
+    cv.iter().enumerate().map(|(i, count)| (i as u32 + 1) * count).sum()
+}
+
+// Rate how well a single guess splits the candidates.
+pub fn rate_guess<'a>(scorer: &Scorer, guess: &'a str, candidates: &[&str]) -> GuessRating<'a> {
+    let map = scorer.score_map(guess, candidates);
+    let cv = map_to_cv(&map);
+
+    GuessRating {
+        guess,
+        is_candidate: candidates.contains(&guess),
+        distinct: map.len(),
+        entropy: cv_entropy(&cv),
+        expected_size: cv_expected_size(&cv),
+        worst_case: cv_worst_case(&cv),
+    }
+}
+
+// Rate a guess from the score matrix against the candidates (answer indices).
+pub fn rate_matrix_guess<'a>(matrix: &ScoreMatrix<'a>, guess: usize, candidates: &[usize], is_candidate: bool) -> GuessRating<'a> {
+    let cv = matrix.cluster_vector(guess, candidates);
+
+    GuessRating {
+        guess: matrix.guesses()[guess],
+        is_candidate,
+        distinct: cv.iter().sum::<u32>() as usize,
+        entropy: cv_entropy(&cv),
+        expected_size: cv_expected_size(&cv),
+        worst_case: cv_worst_case(&cv),
+    }
+}
+
+// Rate a guess when some candidates are more likely than others ('weights' is indexed by answer).
+// Entropy and expected size use each score's share of the total weight; distinct and worst case still count answers.
+pub fn rate_weighted_guess<'a>(matrix: &ScoreMatrix<'a>, guess: usize, candidates: &[usize], weights: &[f64], is_candidate: bool) -> GuessRating<'a> {
+    let row = matrix.row(guess);
+    let mut groups = vec![(0usize, 0.0f64); matrix.distinct(guess)];
+
+    for answer in candidates {
+        let group = &mut groups[row[*answer] as usize];
+        group.0 += 1;
+        group.1 += weights[*answer];
+    }
+
+    // Sum in a fixed order, so guesses which split the candidates the same way get exactly the same rating
+    groups.retain(|(count, _)| *count > 0);
+    groups.sort_by(|a, b| a.1.total_cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+    let total = groups.iter().map(|(_, weight)| weight).sum::<f64>();
+
+    let mut entropy = 0.0;
+    let mut expected_size = 0.0;
+    for (count, weight) in groups.iter() {
+        let p = if total > 0.0 { weight / total } else { 0.0 };
+        if p > 0.0 {
+            entropy -= p * p.log2();
+        }
+
+        expected_size += p * *count as f64;
+    }
+
+    GuessRating {
+        guess: matrix.guesses()[guess],
+        is_candidate,
+        distinct: groups.len(),
+        entropy,
+        expected_size,
+        worst_case: groups.iter().map(|(count, _)| *count).max().unwrap_or(0),
+    }
+}
+
+// Rate every guess in the matrix against the remaining candidates (answer indices) and return them best first.
+// Ties on the chosen measure are broken by the other measures, then by preferring guesses which could be the answer.
+pub fn rank_guesses<'a>(matrix: &ScoreMatrix<'a>, candidates: &[usize], by: RankBy) -> Vec<GuessRating<'a>> {
+    let guesses = (0..matrix.guesses().len()).collect::<Vec<_>>();
+    rank_guess_subset(matrix, &guesses, candidates, None, by)
+}
+
+// Rate only some of the guesses in the matrix (guess indices), best first. Used to limit recommendations in hard mode.
+// With weights (indexed by answer), guesses are rated by how they split the likely answers (see rate_weighted_guess).
+pub fn rank_guess_subset<'a>(matrix: &ScoreMatrix<'a>, guesses: &[usize], candidates: &[usize], weights: Option<&[f64]>, by: RankBy) -> Vec<GuessRating<'a>> {
+    let mut is_candidate = vec![false; matrix.answers().len()];
+    for answer in candidates {
+        is_candidate[*answer] = true;
+    }
+
+    let mut ratings = guesses.iter()
+        .map(|g| {
+            let candidate = matrix.answer_index(matrix.guesses()[*g]).is_some_and(|a| is_candidate[a]);
+            match weights {
+                Some(weights) => rate_weighted_guess(matrix, *g, candidates, weights, candidate),
+                None => rate_matrix_guess(matrix, *g, candidates, candidate),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    ratings.sort_by(|a, b| compare_ratings(a, b, by));
+    ratings
+}
+
+// Order two ratings so that the better guess comes first.
+pub fn compare_ratings(a: &GuessRating, b: &GuessRating, by: RankBy) -> Ordering {
+    let entropy = || b.entropy.total_cmp(&a.entropy);
+    let expected = || a.expected_size.total_cmp(&b.expected_size);
+    let worst = || a.worst_case.cmp(&b.worst_case);
+
+    let order = match by {
+        RankBy::Entropy => entropy().then_with(worst).then_with(expected),
+        RankBy::ExpectedSize => expected().then_with(worst).then_with(entropy),
+        RankBy::WorstCase => worst().then_with(expected).then_with(entropy),
+    };
+
+    order
+        .then_with(|| b.is_candidate.cmp(&a.is_candidate))
+        .then_with(|| a.guess.cmp(b.guess))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answers::ANSWERS;
+    use super::*;
+
+    fn indices(words: &[&str]) -> Vec<usize> {
+        words.iter().map(|word| ANSWERS.binary_search(word).unwrap()).collect()
+    }
+
+    #[test]
+    fn cv_measures() {
+        // Four answers split into singletons: two bits, one answer left
+        let cv = vec![4];
+        assert_eq!(2.0, cv_entropy(&cv));
+        assert_eq!(1.0, cv_expected_size(&cv));
+        assert_eq!(1, cv_worst_case(&cv));
+
+        // Four answers in one group: no information
+        let cv = vec![0, 0, 0, 1];
+        assert_eq!(0.0, cv_entropy(&cv));
+        assert_eq!(4.0, cv_expected_size(&cv));
+        assert_eq!(4, cv_worst_case(&cv));
+
+        // Two singletons and a pair: 1.5 bits, (1 + 1 + 4) / 4 answers left
+        let cv = vec![2, 1];
+        assert_eq!(1.5, cv_entropy(&cv));
+        assert_eq!(1.5, cv_expected_size(&cv));
+        assert_eq!(2, cv_worst_case(&cv));
+    }
+
+    #[test]
+    fn rate_opening_guess() {
+        let rating = rate_guess(Scorer::qwertle(), "papal", ANSWERS);
+        assert_eq!(2118, rating.distinct);
+        assert_eq!(4, rating.worst_case);
+        assert!(rating.is_candidate);
+        assert!(rating.entropy > 10.9 && rating.entropy < 11.2);
+        assert!(rating.expected_size > 1.0 && rating.expected_size < 1.2);
+    }
+
+    #[test]
+    fn rank_mid_game() {
+        let matrix = ScoreMatrix::build(Scorer::qwertle(), ANSWERS, ANSWERS).unwrap();
+        let candidates = indices(&["gamut", "gaunt", "taunt", "vaunt"]);
+
+        let ranked = rank_guesses(&matrix, &candidates, RankBy::Entropy);
+        assert_eq!(ANSWERS.len(), ranked.len());
+
+        // The best guesses split all four and could be the answer themselves
+        let best = &ranked[0];
+        assert_eq!(2.0, best.entropy);
+        assert_eq!(1, best.worst_case);
+        assert!(best.is_candidate);
+        assert_eq!(best, &rate_guess(Scorer::qwertle(), best.guess, &["gamut", "gaunt", "taunt", "vaunt"]));
+
+        // Rankings are ordered by the requested measure
+        let ranked = rank_guesses(&matrix, &candidates, RankBy::WorstCase);
+        assert!(ranked.windows(2).all(|w| w[0].worst_case <= w[1].worst_case));
+
+        let ranked = rank_guesses(&matrix, &candidates, RankBy::ExpectedSize);
+        assert!(ranked.windows(2).all(|w| w[0].expected_size <= w[1].expected_size));
+    }
+
+    #[test]
+    fn rate_weighted() {
+        let matrix = ScoreMatrix::build(Scorer::qwertle(), ANSWERS, ANSWERS).unwrap();
+        let candidates = indices(&["gamut", "gaunt", "taunt", "vaunt"]);
+        let papal = matrix.guess_index("papal").unwrap();
+        let best = rank_guesses(&matrix, &candidates, RankBy::Entropy)[0].guess;
+        let best = matrix.guess_index(best).unwrap();
+
+        // Equal weights rate a guess the same as counting answers
+        let uniform = vec![1.0; ANSWERS.len()];
+        for g in [papal, best] {
+            let counted = rate_matrix_guess(&matrix, g, &candidates, false);
+            let weighted = rate_weighted_guess(&matrix, g, &candidates, &uniform, false);
+            assert_eq!(counted.distinct, weighted.distinct);
+            assert_eq!(counted.worst_case, weighted.worst_case);
+            assert!((counted.entropy - weighted.entropy).abs() < 1e-9);
+            assert!((counted.expected_size - weighted.expected_size).abs() < 1e-9);
+        }
+
+        // When one answer is almost certain, any guess tells us little
+        let mut weights = uniform.clone();
+        weights[candidates[2]] = 1000.0;
+        let rating = rate_weighted_guess(&matrix, best, &candidates, &weights, true);
+        assert_eq!(4, rating.distinct);
+        assert!(rating.entropy < 0.1);
+        assert!(rating.expected_size < 1.01);
+
+        let ranked = rank_guess_subset(&matrix, &[papal, best], &candidates, Some(&weights), RankBy::Entropy);
+        assert_eq!(2, ranked.len());
+        assert!(ranked[0].entropy >= ranked[1].entropy);
+    }
+}
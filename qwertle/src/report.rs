@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+use serde::Serialize;
+use crate::{*, distance::Scorer, matrix::ScoreMatrix, recommend::*};
+
+// How well one opener splits every answer, for comparing all of them side by side.
+//  distinct, entropy, expected_size, worst_case: As in GuessRating, for the first guess of a game.
+//  coverage:  For each position, the share of answers whose letter there is identified by that digit of the score alone.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OpenerStats<'a> {
+    pub guess: &'a str,
+    pub is_answer: bool,
+    pub distinct: usize,
+    pub entropy: f64,
+    pub expected_size: f64,
+    pub worst_case: usize,
+    pub coverage: Vec<f64>,
+}
+
+// Rate every guess in the matrix as an opener, best first by the chosen measure.
+pub fn opener_stats<'a>(matrix: &ScoreMatrix<'a>, by: RankBy) -> Vec<OpenerStats<'a>> {
+    let answers = (0..matrix.answers().len()).collect::<Vec<_>>();
+    let frequencies = letter_frequencies(matrix.answers());
+
+    rank_guesses(matrix, &answers, by)
+        .into_iter()
+        .map(|rating| OpenerStats {
+            guess: rating.guess,
+            is_answer: rating.is_candidate,
+            distinct: rating.distinct,
+            entropy: rating.entropy,
+            expected_size: rating.expected_size,
+            worst_case: rating.worst_case,
+            coverage: position_coverage(matrix.scorer(), rating.guess, &frequencies),
+        })
+        .collect()
+}
+
+// For each position of the guess, the share of answers whose letter there is the only answer letter in that position
+// at its distance from the guess letter. A position scoring 1.0 always tells exactly which letter the answer has there.
+pub fn position_coverage(scorer: &Scorer, guess: &str, frequencies: &HashMap<(char, u8), u16>) -> Vec<f64> {
+    guess.chars().enumerate()
+        .map(|(pos, letter)| {
+            // Digits can pass 9 under some distance models, so count by digit up to the largest a u8 holds
+            let mut letters_at_digit = [0u32; 256];
+            let mut counts = Vec::new();
+
+            for other in 'a'..='z' {
+                let count = frequencies.get(&(other, pos as u8)).copied().unwrap_or(0);
+                if count > 0 {
+                    let digit = scorer.letter_distance(letter, other);
+                    letters_at_digit[digit as usize] += 1;
+                    counts.push((digit, count as u32));
+                }
+            }
+
+            let total = counts.iter().map(|(_, count)| count).sum::<u32>();
+            let identified = counts.iter().filter(|(digit, _)| letters_at_digit[*digit as usize] == 1).map(|(_, count)| count).sum::<u32>();
+            if total > 0 { identified as f64 / total as f64 } else { 0.0 }
+        })
+        .collect()
+}
+
+// A Markdown summary of the report: the best opener by each measure, then the top 'limit' openers in ranked order.
+pub fn summary_markdown(stats: &[OpenerStats], answers: usize, by: RankBy, limit: usize) -> String {
+    let mut text = format!("# Opener Report\n\n{} openers rated against {answers} answers.\n\n", stats.len());
+
+    text += "## Best by Measure\n\n| measure | guess | value |\n|---|---|---|\n";
+    let best = [
+        ("distinct", stats.iter().max_by(|a, b| a.distinct.cmp(&b.distinct).then_with(|| b.guess.cmp(a.guess))).map(|s| (s.guess, s.distinct.to_string()))),
+        ("entropy", stats.iter().max_by(|a, b| a.entropy.total_cmp(&b.entropy).then_with(|| b.guess.cmp(a.guess))).map(|s| (s.guess, format!("{:.4}", s.entropy)))),
+        ("expected_size", stats.iter().min_by(|a, b| a.expected_size.total_cmp(&b.expected_size).then_with(|| a.guess.cmp(b.guess))).map(|s| (s.guess, format!("{:.4}", s.expected_size)))),
+        ("worst_case", stats.iter().min_by(|a, b| a.worst_case.cmp(&b.worst_case).then_with(|| a.guess.cmp(b.guess))).map(|s| (s.guess, s.worst_case.to_string()))),
+        ("coverage", stats.iter().max_by(|a, b| mean(&a.coverage).total_cmp(&mean(&b.coverage)).then_with(|| b.guess.cmp(a.guess))).map(|s| (s.guess, format!("{:.4}", mean(&s.coverage))))),
+    ];
+
+    for (measure, best) in best.iter() {
+        if let Some((guess, value)) = best {
+            text += &format!("| {measure} | {guess} | {value} |\n");
+        }
+    }
+
+    let measure = match by {
+        RankBy::Entropy => "entropy",
+        RankBy::ExpectedSize => "expected size",
+        RankBy::WorstCase => "worst case",
+    };
+
+    text += &format!("\n## Top {} by {measure}\n\n| rank | guess | distinct | entropy | expected_size | worst_case | coverage |\n|---|---|---|---|---|---|---|\n", limit.min(stats.len()));
+    for (rank, s) in stats.iter().take(limit).enumerate() {
+        let coverage = s.coverage.iter().map(|c| format!("{c:.2}")).collect::<Vec<_>>().join(" ");
+        text += &format!("| {} | {} | {} | {:.4} | {:.4} | {} | {coverage} |\n", rank + 1, s.guess, s.distinct, s.entropy, s.expected_size, s.worst_case);
+    }
+
+    text
+}
+
+fn mean(values: &[f64]) -> f64 {
+    if values.is_empty() { 0.0 } else { values.iter().sum::<f64>() / values.len() as f64 }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{answers::ANSWERS, distance::parse_model, keyboard::QWERTY};
+    use super::*;
+
+    #[test]
+    fn coverage_by_position() {
+        // Only 'a' and 'b' in the first position: the digit from 'p' tells them apart, so every answer is identified
+        let frequencies = letter_frequencies(&["ab", "ba"]);
+        assert_eq!(vec![1.0, 1.0], position_coverage(Scorer::qwertle(), "pp", &frequencies));
+
+        // 'w' and 's' are both 1 away from 'a' (see results/papal.txt), so neither is identified, but 'p' is
+        let frequencies = letter_frequencies(&["w", "s", "p", "p"]);
+        assert_eq!(vec![0.5], position_coverage(Scorer::qwertle(), "a", &frequencies));
+    }
+
+    #[test]
+    fn coverage_with_large_digits() {
+        // With 20 px units, 'q' to 'p' is 23 apart
+        let scorer = Scorer::new(QWERTY, parse_model("euclidean:20").unwrap()).unwrap();
+        let frequencies = letter_frequencies(&["p", "o", "a"]);
+        assert_eq!(vec![1.0], position_coverage(&scorer, "q", &frequencies));
+
+        let answers = &ANSWERS[..200];
+        let matrix = ScoreMatrix::build(&Scorer::new(QWERTY, parse_model("manhattan").unwrap()).unwrap(), answers, answers).unwrap();
+        let stats = opener_stats(&matrix, RankBy::Entropy);
+        assert_eq!(answers.len(), stats.len());
+        assert!(stats.iter().all(|s| s.coverage.iter().all(|c| (0.0..=1.0).contains(c))));
+    }
+
+    #[test]
+    fn rate_all_openers() {
+        let matrix = ScoreMatrix::build(Scorer::qwertle(), ANSWERS, ANSWERS).unwrap();
+        let stats = opener_stats(&matrix, RankBy::Entropy);
+        assert_eq!(ANSWERS.len(), stats.len());
+        assert!(stats.windows(2).all(|pair| pair[0].entropy >= pair[1].entropy));
+
+        let papal = stats.iter().find(|s| s.guess == "papal").unwrap();
+        assert_eq!(2118, papal.distinct);
+        assert_eq!(4, papal.worst_case);
+        assert_eq!(5, papal.coverage.len());
+
+        let summary = summary_markdown(&stats, ANSWERS.len(), RankBy::Entropy, 3);
+        assert!(summary.contains(&format!("{} openers rated against {} answers.", ANSWERS.len(), ANSWERS.len())));
+        assert!(summary.contains(&format!("| entropy | {} |", stats[0].guess)));
+        assert!(summary.contains(&format!("| 3 | {} |", stats[2].guess)));
+        assert!(!summary.contains(&format!("| 4 | {} |", stats[3].guess)));
+    }
+}
@@ -0,0 +1,231 @@
+use std::{fmt, str::FromStr};
+use serde::{Serialize, Serializer};
+use crate::error::QwertleError;
+
+// The longest word a Score can hold a distance for.
+pub const MAX_WORD_LENGTH: usize = 16;
+
+// The response to a guess: the quantized keyboard distance from each guess letter to the answer letter.
+// Scores are shown as one digit per letter (ex: 00265). If any distance is over 9 (possible with some
+// distance models), the distances are separated with dots instead (ex: 0.0.2.11.5).
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Score {
+    len: u8,
+    digits: [u8; MAX_WORD_LENGTH],
+}
+
+impl Score {
+    // Build a Score from the distance for each letter. Fails for words over MAX_WORD_LENGTH letters.
+    pub fn new(digits: &[u8]) -> Result<Score, QwertleError> {
+        let mut score = Score::default();
+        for digit in digits {
+            score.push(*digit)?;
+        }
+
+        Ok(score)
+    }
+
+    // Build a Score from the distances for a word, without the length check in Score::new, for scoring millions of words.
+    // Callers must check words are at most MAX_WORD_LENGTH letters first (see error::validate_word); longer ones panic
+    // rather than being cut short.
+    pub(crate) fn from_distances(distances: impl Iterator<Item = u8>) -> Score {
+        let mut score = Score::default();
+        for digit in distances {
+            score.digits[score.len as usize] = digit;
+            score.len += 1;
+        }
+
+        score
+    }
+
+    // Add the distance for the next letter. Fails if the Score already has MAX_WORD_LENGTH distances.
+    pub fn push(&mut self, digit: u8) -> Result<(), QwertleError> {
+        if self.len as usize >= MAX_WORD_LENGTH {
+            return Err(QwertleError::MalformedScore { score: self.to_string(), reason: format!("can't have more than {MAX_WORD_LENGTH} digits.") });
+        }
+
+        self.digits[self.len as usize] = digit;
+        self.len += 1;
+        Ok(())
+    }
+
+    pub fn digits(&self) -> &[u8] {
+        &self.digits[..self.len as usize]
+    }
+
+    pub fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // Whether every letter was correct (the guess was the answer).
+    pub fn is_solved(&self) -> bool {
+        self.digits().iter().all(|digit| *digit == 0)
+    }
+
+    // Keep only the distances for the last 'count' letters.
+    pub fn last(&self, count: usize) -> Score {
+        let digits = self.digits();
+        Score::from_distances(digits[digits.len().saturating_sub(count)..].iter().copied())
+    }
+
+    // Compute the distance between two scores.
+    // This is the sum of the absolute difference between each digit. Scores of different
+    // lengths are lined up by their last digit, with missing leading digits counted as zero.
+    pub fn distance(&self, other: &Score) -> u32 {
+        let length = self.len().max(other.len());
+        let padded = |score: &Score, i: usize| {
+            let offset = length - score.len();
+            if i < offset { 0 } else { score.digits[i - offset] }
+        };
+
+        (0..length).map(|i| padded(self, i).abs_diff(padded(other, i)) as u32).sum()
+    }
+}
+
+impl Default for Score {
+    fn default() -> Self {
+        Score { len: 0, digits: [0; MAX_WORD_LENGTH] }
+    }
+}
+
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let separator = if self.digits().iter().any(|digit| *digit > 9) { "." } else { "" };
+
+        for (i, digit) in self.digits().iter().enumerate() {
+            if i > 0 {
+                write!(f, "{separator}")?;
+            }
+            write!(f, "{digit}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Score {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Score({self})")
+    }
+}
+
+// Scores are written in JSON as their text form (ex: "00265").
+impl Serialize for Score {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+// Characters which may separate the distances in a score, so distances above 9 can be written (ex: 0.12.3).
+pub(crate) fn is_separator(c: char) -> bool {
+    matches!(c, '.' | ',' | '-' | '/') || c.is_whitespace()
+}
+
+// Parse a score as one digit per letter (00265), or with distances separated by '.', ',', '-', '/' or spaces (0.0.2.11.5).
+impl FromStr for Score {
+    type Err = QwertleError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim();
+        let malformed = |reason: String| QwertleError::MalformedScore { score: text.to_string(), reason };
+
+        let digits = if text.contains(is_separator) {
+            text.split(is_separator)
+                .filter(|part| !part.is_empty())
+                .map(|part| part.parse::<u8>().map_err(|_| malformed(format!("has '{part}', which is not a distance."))))
+                .collect::<Result<Vec<_>, _>>()?
+        } else {
+            text.chars()
+                .map(|c| c.to_digit(10).map(|d| d as u8).ok_or_else(|| malformed(format!("has an unexpected '{c}'; scores are digits, like 00265."))))
+                .collect::<Result<Vec<_>, _>>()?
+        };
+
+        if digits.is_empty() || digits.len() > MAX_WORD_LENGTH {
+            return Err(malformed(format!("must have between one and {MAX_WORD_LENGTH} digits.")));
+        }
+
+        Score::new(&digits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn score_length_limit() {
+        let mut score = Score::new(&[1; MAX_WORD_LENGTH]).unwrap();
+        assert!(score.push(2).is_err());
+        assert_eq!(MAX_WORD_LENGTH, score.len());
+        assert!(matches!(Score::new(&[1; MAX_WORD_LENGTH + 1]), Err(QwertleError::MalformedScore { .. })));
+
+        // Longer words are rejected rather than scored on their first MAX_WORD_LENGTH letters
+        let long = "a".repeat(MAX_WORD_LENGTH + 1);
+        assert!(crate::distance::Scorer::qwertle().word_distance(&long, &long).is_err());
+        assert!(crate::word_distance(&long, &long).is_err());
+
+        let longest = "a".repeat(MAX_WORD_LENGTH);
+        assert_eq!(MAX_WORD_LENGTH, crate::word_distance(&longest, &longest).unwrap().len());
+        assert_eq!(&[1, 2], Score::new(&[[0; MAX_WORD_LENGTH - 2].as_slice(), &[1, 2]].concat()).unwrap().last(2).digits());
+    }
+
+    #[test]
+    fn score_format_and_parse() {
+        let score = "00265".parse::<Score>().unwrap();
+        assert_eq!(&[0, 0, 2, 6, 5], score.digits());
+        assert_eq!("00265", score.to_string());
+        assert_eq!(5, score.len());
+
+        let long = Score::new(&[0, 0, 2, 11, 5, 3, 1]).unwrap();
+        assert_eq!("0.0.2.11.5.3.1", long.to_string());
+        assert_eq!(long, "0.0.2.11.5.3.1".parse().unwrap());
+        assert_eq!(long, "0, 0, 2, 11, 5, 3, 1".parse().unwrap());
+
+        // Other common separators are accepted too
+        assert_eq!(score, "0-0-2-6-5".parse().unwrap());
+        assert_eq!(score, " 0/0/2/6/5\n".parse().unwrap());
+        assert_eq!(score, "0\t0 2  6 5".parse().unwrap());
+
+        assert!("".parse::<Score>().is_err());
+        assert!("12a45".parse::<Score>().is_err());
+        assert!("1.300".parse::<Score>().is_err());
+        assert!("12345678901234567".parse::<Score>().is_err());
+        assert_eq!("Score '12a45' has an unexpected 'a'; scores are digits, like 00265.", "12a45".parse::<Score>().unwrap_err().to_string());
+        assert_eq!("Score '1.300' has '300', which is not a distance.", "1.300".parse::<Score>().unwrap_err().to_string());
+
+        assert_eq!("\"00265\"", serde_json::to_string(&score).unwrap());
+    }
+
+    #[test]
+    fn score_order_and_solved() {
+        let a = "00265".parse::<Score>().unwrap();
+        let b = "01000".parse::<Score>().unwrap();
+        assert!(a < b);
+
+        assert!("00000".parse::<Score>().unwrap().is_solved());
+        assert!(!a.is_solved());
+
+        assert_eq!("65", a.last(2).to_string());
+        assert_eq!(a, a.last(9));
+    }
+
+    #[test]
+    fn score_distances() {
+        let score = |text: &str| text.parse::<Score>().unwrap();
+
+        assert_eq!(0, score("52321").distance(&score("52321")));
+        assert_eq!(1, score("51321").distance(&score("52321")));
+        assert_eq!(1, score("53321").distance(&score("52321")));
+        assert_eq!(3, score("51411").distance(&score("52321")));
+        assert_eq!(3, score("82321").distance(&score("52321")));
+        assert_eq!(13, score("00000").distance(&score("52321")));
+
+        // Shorter scores line up with the last digits
+        assert_eq!(0, score("21").distance(&score("00021")));
+        assert_eq!(3, score("21").distance(&score("00321")));
+    }
+}
@@ -0,0 +1,365 @@
+use std::{collections::{BTreeMap, HashMap}, io::{self, BufRead, BufReader, Write}, net::{TcpListener, TcpStream}, time::Duration};
+use serde::Serialize;
+use crate::{*, distance::Scorer, output::*, priors::Priors};
+
+// A minimal HTTP/1.1 server answering GET requests with JSON, so browser extensions and bots can use the solver.
+//  GET /distances?g=papal               Every score for the guess, with the answers getting each one (word_distance_map).
+//  GET /assess?g=papal&s=00265[&w=2]    Answers whose score for the guess is within 'w' of the score (answer_options).
+//  GET /letters?g=papal&s=00265         Letters each guess letter could be for the score (letter_options).
+//  GET /table?g=papal                   Letters at each distance from each guess letter (letter_table).
+//  GET /health                          Check the server is up.
+// Each connection is answered and then closed. Errors are returned as { "error": "..." }.
+
+// How long to wait for a client to send its request before giving up on it.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub struct HttpRequest {
+    pub path: String,
+    pub arguments: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HttpStatus {
+    Ok,
+    BadRequest,
+    NotFound,
+    MethodNotAllowed,
+}
+
+impl HttpStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HttpStatus::Ok               => "200 OK",
+            HttpStatus::BadRequest       => "400 Bad Request",
+            HttpStatus::NotFound         => "404 Not Found",
+            HttpStatus::MethodNotAllowed => "405 Method Not Allowed",
+        }
+    }
+}
+
+pub struct HttpResponse {
+    pub status: HttpStatus,
+    pub body: String,
+}
+
+impl HttpResponse {
+    pub fn json<T: Serialize>(value: &T) -> HttpResponse {
+        HttpResponse { status: HttpStatus::Ok, body: serde_json::to_string(value).unwrap() }
+    }
+
+    pub fn error(status: HttpStatus, message: &str) -> HttpResponse {
+        let body = serde_json::json!({ "error": message }).to_string();
+        HttpResponse { status, body }
+    }
+}
+
+#[derive(Serialize)]
+struct DistancesResponse<'a> {
+    guess: String,
+    distinct: usize,
+    cv: Vec<u32>,
+    scores: BTreeMap<String, Vec<&'a str>>,
+}
+
+#[derive(Serialize)]
+struct LettersResponse {
+    #[serde(flatten)]
+    options: LetterOptionsReport,
+    text: String,
+}
+
+#[derive(Serialize)]
+struct TableResponse {
+    guess: String,
+    rows: Vec<LetterTableRow>,
+    text: String,
+}
+
+// The answers and scoring the server uses, shared by every request.
+pub struct Server<'a> {
+    scorer: Scorer,
+    answers: Vec<&'a str>,
+    frequencies: HashMap<(char, u8), u16>,
+    word_length: Option<usize>,
+}
+
+impl<'a> Server<'a> {
+    pub fn new(scorer: &Scorer, answers: &[&'a str]) -> Server<'a> {
+        let word_length = answers.first().map(|answer| answer.len());
+        Server { scorer: scorer.clone(), answers: answers.to_vec(), frequencies: letter_frequencies(answers), word_length }
+    }
+
+    // Answer each connection on the listener in turn until the process is stopped, writing a line per request to 'log'.
+    pub fn run(&self, listener: TcpListener, log: &mut impl Write) -> io::Result<()> {
+        for stream in listener.incoming() {
+            if let Err(error) = self.answer(stream?, log) {
+                writeln!(log, "  Connection failed: {error}")?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Read one request from the connection, write the response, and close it. Logs the path and response status.
+    pub fn answer(&self, mut stream: TcpStream, log: &mut impl Write) -> io::Result<()> {
+        stream.set_read_timeout(Some(READ_TIMEOUT))?;
+
+        let (path, response) = match read_request(&mut BufReader::new(&stream)) {
+            Ok(request) => (request.path.clone(), self.handle(&request)),
+            Err(response) => ("(unreadable request)".to_string(), response),
+        };
+
+        writeln!(log, "{path} -> {} ({} bytes)", response.status.as_str(), response.body.len())?;
+        write_response(&mut stream, &response)
+    }
+
+    // Route a request to the endpoint for its path.
+    pub fn handle(&self, request: &HttpRequest) -> HttpResponse {
+        let result = match request.path.as_str() {
+            "/health" => Ok(HttpResponse::json(&serde_json::json!({ "status": "ok", "answers": self.answers.len() }))),
+            "/distances" => self.distances(request),
+            "/assess" => self.assess(request),
+            "/letters" => self.letters(request),
+            "/table" => self.table(request),
+            _ => Err(HttpResponse::error(HttpStatus::NotFound, &format!("No endpoint at '{}'.", request.path))),
+        };
+
+        result.unwrap_or_else(|response| response)
+    }
+
+    fn distances(&self, request: &HttpRequest) -> Result<HttpResponse, HttpResponse> {
+        let guess = guess_argument(request, self.word_length)?;
+        let map = self.scorer.score_map(&guess, &self.answers);
+
+        Ok(HttpResponse::json(&DistancesResponse {
+            distinct: map.len(),
+            cv: map_to_cv(&map),
+            scores: map.into_iter().map(|(score, answers)| (score.to_string(), answers)).collect(),
+            guess,
+        }))
+    }
+
+    fn assess(&self, request: &HttpRequest) -> Result<HttpResponse, HttpResponse> {
+        let guess = guess_argument(request, self.word_length)?;
+        let score = score_argument(request)?;
+        let within = match request.arguments.get("w") {
+            None => 2,
+            Some(text) => text.parse::<u32>().map_err(|_| HttpResponse::error(HttpStatus::BadRequest, &format!("'w' must be a number, not '{text}'.")))?,
+        };
+
+        Ok(HttpResponse::json(&answer_options_report(&self.scorer, &guess, &score, &self.answers, within, &Priors::uniform())))
+    }
+
+    fn letters(&self, request: &HttpRequest) -> Result<HttpResponse, HttpResponse> {
+        let guess = guess_argument(request, self.word_length)?;
+        let score = score_argument(request)?;
+
+        let options = letter_options_report(&self.scorer, &guess, &score, &self.frequencies);
+        let text = self.scorer.letter_options(&guess, &score, &self.frequencies).map_err(|error| HttpResponse::error(HttpStatus::BadRequest, &error.to_string()))?;
+        Ok(HttpResponse::json(&LettersResponse { options, text }))
+    }
+
+    fn table(&self, request: &HttpRequest) -> Result<HttpResponse, HttpResponse> {
+        let guess = guess_argument(request, self.word_length)?;
+
+        let rows = letter_table_rows(&self.scorer, &guess, &self.frequencies);
+        let text = self.scorer.letter_table(&guess, &self.answers).map_err(|error| HttpResponse::error(HttpStatus::BadRequest, &error.to_string()))?;
+        Ok(HttpResponse::json(&TableResponse { guess, rows, text }))
+    }
+}
+
+// Read the guess ('g'), which must be letters only and, like guesses typed at the command line, the answers' length.
+fn guess_argument(request: &HttpRequest, word_length: Option<usize>) -> Result<String, HttpResponse> {
+    let guess = request.arguments.get("g").ok_or(HttpResponse::error(HttpStatus::BadRequest, "Guess 'g' not provided."))?;
+    let guess = match word_length {
+        Some(length) => validate_word_length(guess, length),
+        None => validate_word(guess),
+    };
+
+    guess.map_err(|error| HttpResponse::error(HttpStatus::BadRequest, &error.to_string()))
+}
+
+// Read the score ('s').
+fn score_argument(request: &HttpRequest) -> Result<Score, HttpResponse> {
+    let text = request.arguments.get("s").ok_or(HttpResponse::error(HttpStatus::BadRequest, "Score 's' not provided."))?;
+    text.parse::<Score>().map_err(|error| HttpResponse::error(HttpStatus::BadRequest, &error.to_string()))
+}
+
+// Read the request line (ex: "GET /assess?g=papal&s=00265 HTTP/1.1") and skip the headers.
+// Headers must be read so the client sees the response rather than a reset connection.
+fn read_request(reader: &mut impl BufRead) -> Result<HttpRequest, HttpResponse> {
+    let bad_request = |message: &str| HttpResponse::error(HttpStatus::BadRequest, message);
+
+    let mut line = String::new();
+    reader.read_line(&mut line).map_err(|_| bad_request("Unable to read request."))?;
+
+    loop {
+        let mut header = String::new();
+        match reader.read_line(&mut header) {
+            Ok(0) => break,
+            Ok(_) if header.trim_end().is_empty() => break,
+            Ok(_) => continue,
+            Err(_) => return Err(bad_request("Unable to read request headers.")),
+        }
+    }
+
+    let parts = line.split_whitespace().collect::<Vec<_>>();
+    if parts.len() != 3 || !parts[2].starts_with("HTTP/") {
+        return Err(bad_request("Request line must be '<verb> <path> HTTP/1.1'."));
+    }
+
+    if parts[0] != "GET" {
+        return Err(HttpResponse::error(HttpStatus::MethodNotAllowed, &format!("Only GET is supported, not {}.", parts[0])));
+    }
+
+    let (path, query) = parts[1].split_once('?').unwrap_or((parts[1], ""));
+    let arguments = parse_query(query).ok_or(bad_request("Unable to parse querystring."))?;
+
+    Ok(HttpRequest { path: path.to_string(), arguments })
+}
+
+// Parse 'name=value&name=value' arguments, decoding '+' and %XX escapes.
+fn parse_query(query: &str) -> Option<HashMap<String, String>> {
+    let mut arguments = HashMap::new();
+
+    for argument in query.split('&').filter(|a| !a.is_empty()) {
+        let (name, value) = argument.split_once('=').unwrap_or((argument, ""));
+        arguments.insert(decode(name)?, decode(value)?);
+    }
+
+    Some(arguments)
+}
+
+fn decode(text: &str) -> Option<String> {
+    let mut bytes = Vec::new();
+    let mut input = text.bytes();
+
+    while let Some(b) = input.next() {
+        match b {
+            b'+' => bytes.push(b' '),
+            b'%' => {
+                let hex = [input.next()?, input.next()?];
+                bytes.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+            }
+            _ => bytes.push(b),
+        }
+    }
+
+    String::from_utf8(bytes).ok()
+}
+
+fn write_response(stream: &mut TcpStream, response: &HttpResponse) -> io::Result<()> {
+    let status = response.status.as_str();
+    let length = response.body.len();
+
+    // Allow any origin, so browser extensions and pages can call the API
+    let head = format!("HTTP/1.1 {status}\r\nContent-Type: application/json; charset=utf-8\r\nContent-Length: {length}\r\nAccess-Control-Allow-Origin: *\r\nConnection: close\r\n\r\n");
+
+    stream.write_all(head.as_bytes())?;
+    stream.write_all(response.body.as_bytes())?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Read, thread};
+    use serde_json::Value;
+    use crate::answers::ANSWERS;
+    use super::*;
+
+    fn get(server: &Server, url: &str) -> (HttpStatus, Value) {
+        let request = read_request(&mut format!("GET {url} HTTP/1.1\r\nHost: localhost\r\n\r\n").as_bytes());
+        let response = match request {
+            Ok(request) => server.handle(&request),
+            Err(response) => response,
+        };
+
+        (response.status, serde_json::from_str(&response.body).unwrap())
+    }
+
+    #[test]
+    fn parse_requests() {
+        let request = read_request(&mut "GET /assess?g=papal&s=0%2C0%2C2+6+5 HTTP/1.1\r\n\r\n".as_bytes()).ok().unwrap();
+        assert_eq!("/assess", request.path);
+        assert_eq!("papal", request.arguments["g"]);
+        assert_eq!("0,0,2 6 5", request.arguments["s"]);
+
+        assert!(read_request(&mut "GET /health\r\n\r\n".as_bytes()).is_err());
+        assert!(read_request(&mut "GET /assess?g=%2 HTTP/1.1\r\n\r\n".as_bytes()).is_err());
+
+        let post = read_request(&mut "POST /assess HTTP/1.1\r\n\r\n".as_bytes());
+        assert_eq!(HttpStatus::MethodNotAllowed, post.err().unwrap().status);
+    }
+
+    #[test]
+    fn endpoints() {
+        let server = Server::new(Scorer::qwertle(), ANSWERS);
+
+        let (status, json) = get(&server, "/distances?g=papal");
+        assert_eq!(HttpStatus::Ok, status);
+        assert_eq!(2118, json["distinct"]);
+        assert_eq!(Value::from(vec!["gamut", "gaunt", "taunt", "vaunt"]), json["scores"]["50365"]);
+
+        let (_, json) = get(&server, "/assess?g=papal&s=50365&w=0");
+        let answers = json["answers"].as_array().unwrap();
+        assert_eq!(4, answers.len());
+        assert_eq!("gamut", answers[0]["answer"]);
+        assert_eq!(0, answers[0]["distance"]);
+
+        let (_, json) = get(&server, "/letters?g=apple&s=32521");
+        assert_eq!(5, json["positions"].as_array().unwrap().len());
+        assert!(json["positions"][0]["options"].as_str().unwrap().contains('r'));
+        assert_eq!(Value::from(letter_options("apple", &"32521".parse().unwrap(), &letter_frequencies(ANSWERS)).unwrap()), json["text"]);
+
+        let (_, json) = get(&server, "/table?g=apple");
+        assert_eq!("a", json["rows"][0]["by_distance"][0]);
+        assert_eq!(Value::from(letter_table("apple", ANSWERS).unwrap()), json["text"]);
+
+        let (status, json) = get(&server, "/health");
+        assert_eq!(HttpStatus::Ok, status);
+        assert_eq!(2315, json["answers"]);
+    }
+
+    #[test]
+    fn endpoint_errors() {
+        let server = Server::new(Scorer::qwertle(), ANSWERS);
+
+        let urls = ["/assess?g=papal", "/assess?s=00265", "/assess?g=pa1al&s=00265", "/assess?g=papal&s=0x265", "/assess?g=papal&s=00265&w=far", "/distances?g=pa", "/table?g=papals", "/letters?g=pap&s=002"];
+        for url in urls {
+            let (status, json) = get(&server, url);
+            assert_eq!(HttpStatus::BadRequest, status, "{url}");
+            assert!(json["error"].is_string());
+        }
+
+        assert_eq!(HttpStatus::NotFound, get(&server, "/solve").0);
+    }
+
+    #[test]
+    fn serve_on_localhost() {
+        let server = Server::new(Scorer::qwertle(), ANSWERS);
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                let (stream, _) = listener.accept().unwrap();
+                let mut log = Vec::new();
+                server.answer(stream, &mut log).unwrap();
+                assert_eq!("/assess -> 200 OK", String::from_utf8(log).unwrap().split(" (").next().unwrap());
+            });
+
+            let mut client = TcpStream::connect(address).unwrap();
+            client.write_all(b"GET /assess?g=papal&s=50365&w=0 HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+
+            let mut response = String::new();
+            client.read_to_string(&mut response).unwrap();
+
+            assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+            assert!(response.contains("Content-Type: application/json"));
+
+            let (_, body) = response.split_once("\r\n\r\n").unwrap();
+            let json: Value = serde_json::from_str(body).unwrap();
+            assert_eq!(4, json["answers"].as_array().unwrap().len());
+        });
+    }
+}
@@ -0,0 +1,244 @@
+use std::{collections::HashMap, time::{Duration, Instant}};
+use crate::{error::*, game::MAX_GUESSES, matrix::ScoreMatrix, recommend::*, score::Score, tree::splits};
+
+// Games are stopped after this many guesses, so a strategy which stops making progress can't loop forever.
+const TURN_LIMIT: usize = 20;
+
+// A way of choosing the next guess during a game.
+// Strategies must be deterministic: the same history must always get the same guess. The simulator
+// relies on this to choose each guess once for every game which reaches the same position.
+pub trait Strategy {
+    fn name(&self) -> String;
+
+    // Choose the next guess, given the answers still possible (indices into matrix.answers()) and the turns so far.
+    fn choose(&self, matrix: &ScoreMatrix, candidates: &[usize], history: &[(String, Score)]) -> String;
+}
+
+// Guess the first answer still possible. A baseline which uses the scores but plans nothing.
+pub struct FirstCandidate;
+
+impl Strategy for FirstCandidate {
+    fn name(&self) -> String {
+        "first".to_string()
+    }
+
+    fn choose(&self, matrix: &ScoreMatrix, candidates: &[usize], _: &[(String, Score)]) -> String {
+        matrix.answers()[candidates[0]].to_string()
+    }
+}
+
+// Guess the best guess by a ranking measure (like Solver::recommend), or one of the answers once only one or two remain.
+// Ranking by worst case is minimax: it minimizes the largest set of answers which could be left.
+pub struct Ranked {
+    pub by: RankBy,
+}
+
+impl Strategy for Ranked {
+    fn name(&self) -> String {
+        match self.by {
+            RankBy::Entropy => "entropy",
+            RankBy::ExpectedSize => "expected",
+            RankBy::WorstCase => "worst",
+        }.to_string()
+    }
+
+    fn choose(&self, matrix: &ScoreMatrix, candidates: &[usize], _: &[(String, Score)]) -> String {
+        if candidates.len() <= 2 {
+            return matrix.answers()[candidates[0]].to_string();
+        }
+
+        // Like DecisionTree, fall back to guessing the answers one at a time when the best guess can't split them
+        let guess = rank_guesses(matrix, candidates, self.by)[0].guess;
+        if splits(matrix, guess, candidates) { guess } else { matrix.answers()[candidates[0]] }.to_string()
+    }
+}
+
+// Always open with the same word, then follow another strategy.
+pub struct FixedOpener {
+    pub opener: String,
+    pub then: Box<dyn Strategy>,
+}
+
+impl Strategy for FixedOpener {
+    fn name(&self) -> String {
+        format!("{}:{}", self.then.name(), self.opener)
+    }
+
+    fn choose(&self, matrix: &ScoreMatrix, candidates: &[usize], history: &[(String, Score)]) -> String {
+        if history.is_empty() {
+            self.opener.clone()
+        } else {
+            self.then.choose(matrix, candidates, history)
+        }
+    }
+}
+
+// Parse a strategy: 'entropy', 'expected', 'worst' (or 'minimax'), or 'first', with an optional fixed opener (ex: 'entropy:papal').
+// The opener must be one of the matrix's guesses, so it is the answers' length.
+pub fn parse_strategy(text: &str, matrix: &ScoreMatrix) -> Result<Box<dyn Strategy>, QwertleError> {
+    let (name, opener) = match text.split_once(':') {
+        Some((name, opener)) => (name, Some(opener)),
+        None => (text, None),
+    };
+
+    let strategy: Box<dyn Strategy> = match name.to_ascii_lowercase().as_str() {
+        "first" => Box::new(FirstCandidate),
+        other => match RankBy::parse(other) {
+            Some(by) => Box::new(Ranked { by }),
+            None => return Err(QwertleError::InvalidInput(format!("Unknown strategy '{name}'. Use 'entropy', 'expected', 'worst', or 'first'."))),
+        },
+    };
+
+    let opener = match opener {
+        None => return Ok(strategy),
+        Some(opener) => match matrix.answers().first() {
+            Some(answer) => validate_word_length(opener, answer.len())?,
+            None => validate_word(opener)?,
+        },
+    };
+
+    if matrix.guess_index(&opener).is_none() {
+        return Err(QwertleError::UnknownWord(opener));
+    }
+
+    Ok(Box::new(FixedOpener { opener, then: strategy }))
+}
+
+// One simulated game: the guesses made, and whether the last one was the answer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameResult<'a> {
+    pub answer: &'a str,
+    pub guesses: Vec<String>,
+    pub solved: bool,
+}
+
+// The results of playing every answer with one strategy.
+#[derive(Debug, Clone)]
+pub struct Simulation<'a> {
+    pub strategy: String,
+    pub games: Vec<GameResult<'a>>,
+    pub elapsed: Duration,
+}
+
+impl<'a> Simulation<'a> {
+    // Entry H[i] is how many games were solved on guess i+1, including those over MAX_GUESSES.
+    pub fn histogram(&self) -> Vec<usize> {
+        let mut histogram = Vec::new();
+
+        for game in self.games.iter().filter(|game| game.solved) {
+            while histogram.len() < game.guesses.len() {
+                histogram.push(0);
+            }
+
+            histogram[game.guesses.len() - 1] += 1;
+        }
+
+        histogram
+    }
+
+    // Games which needed more than MAX_GUESSES guesses, or were never solved.
+    pub fn failures(&self) -> Vec<&GameResult<'a>> {
+        self.games.iter().filter(|game| !game.solved || game.guesses.len() > MAX_GUESSES).collect()
+    }
+
+    // Average guesses across the solved games.
+    pub fn average_guesses(&self) -> f64 {
+        let solved = self.games.iter().filter(|game| game.solved).collect::<Vec<_>>();
+        if solved.is_empty() {
+            return 0.0;
+        }
+
+        solved.iter().map(|game| game.guesses.len()).sum::<usize>() as f64 / solved.len() as f64
+    }
+
+    pub fn max_guesses(&self) -> usize {
+        self.histogram().len()
+    }
+}
+
+// Play every answer in the matrix as the hidden answer, choosing guesses with the strategy.
+pub fn simulate<'a>(matrix: &ScoreMatrix<'a>, strategy: &dyn Strategy) -> Simulation<'a> {
+    let start = Instant::now();
+    let mut chosen = HashMap::new();
+
+    let games = (0..matrix.answers().len())
+        .map(|answer| play(matrix, strategy, answer, &mut chosen))
+        .collect();
+
+    Simulation { strategy: strategy.name(), games, elapsed: start.elapsed() }
+}
+
+// Play one game, reusing the guess already chosen for any history seen in an earlier game.
+fn play<'a>(matrix: &ScoreMatrix<'a>, strategy: &dyn Strategy, answer: usize, chosen: &mut HashMap<Vec<(String, Score)>, String>) -> GameResult<'a> {
+    let mut candidates = (0..matrix.answers().len()).collect::<Vec<_>>();
+    let mut history: Vec<(String, Score)> = Vec::new();
+
+    while history.len() < TURN_LIMIT && !candidates.is_empty() {
+        let guess = chosen.entry(history.clone()).or_insert_with(|| strategy.choose(matrix, &candidates, &history)).clone();
+        let score = matrix.scorer().score_words(&guess, matrix.answers()[answer]);
+
+        candidates.retain(|a| matrix.scorer().score_words(&guess, matrix.answers()[*a]) == score);
+        history.push((guess, score));
+
+        if score.is_solved() {
+            break;
+        }
+    }
+
+    let solved = history.last().is_some_and(|(_, score)| score.is_solved());
+    GameResult { answer: matrix.answers()[answer], guesses: history.into_iter().map(|(guess, _)| guess).collect(), solved }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{answers::ANSWERS, distance::Scorer, tree::DecisionTree};
+    use super::*;
+
+    #[test]
+    fn parse_strategies() {
+        let matrix = ScoreMatrix::build(Scorer::qwertle(), ANSWERS, ANSWERS).unwrap();
+        assert_eq!("entropy", parse_strategy("entropy", &matrix).unwrap().name());
+        assert_eq!("worst", parse_strategy("minimax", &matrix).unwrap().name());
+        assert_eq!("first:papal", parse_strategy("first:PAPAL", &matrix).unwrap().name());
+
+        assert!(parse_strategy("random", &matrix).is_err());
+        assert!(parse_strategy("entropy:", &matrix).is_err());
+        assert!(parse_strategy("entropy:pa1al", &matrix).is_err());
+
+        // Openers must be guesses of the answers' length
+        assert_eq!(Err(QwertleError::WrongLength { word: "apples".to_string(), expected: 5 }), parse_strategy("entropy:apples", &matrix).map(|s| s.name()));
+        assert_eq!(Err(QwertleError::UnknownWord("qwert".to_string())), parse_strategy("entropy:qwert", &matrix).map(|s| s.name()));
+    }
+
+    #[test]
+    fn simulation_matches_tree() {
+        // A ranked strategy with a fixed opener plays the same games as the strategy tree for that opener
+        let matrix = ScoreMatrix::build(Scorer::qwertle(), ANSWERS, ANSWERS).unwrap();
+        let simulation = simulate(&matrix, parse_strategy("entropy:papal", &matrix).unwrap().as_ref());
+        let stats = DecisionTree::build(&matrix, "papal", RankBy::Entropy).unwrap().stats();
+
+        assert_eq!(ANSWERS.len(), simulation.games.len());
+        assert_eq!(stats.histogram, simulation.histogram());
+        assert_eq!(stats.max_guesses, simulation.max_guesses());
+        assert!((stats.average_guesses - simulation.average_guesses()).abs() < 1e-9);
+        assert!(simulation.failures().is_empty());
+    }
+
+    #[test]
+    fn first_candidate_games() {
+        let answers = ["taunt", "gamut", "gaunt", "vaunt"];
+        let matrix = ScoreMatrix::build(Scorer::qwertle(), &answers, &answers).unwrap();
+        let simulation = simulate(&matrix, &FirstCandidate);
+
+        // 'taunt' scores differently for each other answer, so each is solved on the second guess
+        assert_eq!(vec![1, 3], simulation.histogram());
+        assert_eq!(vec!["taunt", "gamut"], simulation.games[1].guesses);
+        assert!(simulation.games.iter().all(|game| game.solved));
+
+        // An opener which isn't an answer adds a guess to every game
+        let opener = FixedOpener { opener: "papal".to_string(), then: Box::new(FirstCandidate) };
+        let simulation = simulate(&matrix, &opener);
+        assert_eq!(vec![0, 1, 3], simulation.histogram());
+        assert_eq!(2.75, simulation.average_guesses());
+    }
+}
@@ -0,0 +1,235 @@
+use crate::{error::*, hard_mode::*, matrix::ScoreMatrix, priors::Priors, recommend::*, score::Score};
+
+// Track the answers which are still possible as guesses and scores are played during a game.
+// Each turn keeps only the candidates which would have produced the observed score for the guess.
+// Recommendations rate every guess in the score matrix, which also decides the keyboard and distance model.
+// Answers are equally likely unless priors are set, in which case candidates are listed most likely first.
+pub struct Solver<'a> {
+    matrix: &'a ScoreMatrix<'a>,
+    candidates: Vec<usize>,
+    weights: Option<Vec<f64>>,
+    history: Vec<(String, Score)>,
+    rank_by: RankBy,
+    hard_mode: bool,
+}
+
+impl<'a> Solver<'a> {
+    // Start a game where any answer in the matrix is possible.
+    pub fn new(matrix: &'a ScoreMatrix<'a>) -> Solver<'a> {
+        Solver {
+            matrix,
+            candidates: (0..matrix.answers().len()).collect(),
+            weights: None,
+            history: Vec::new(),
+            rank_by: RankBy::Entropy,
+            hard_mode: false,
+        }
+    }
+
+    // Choose which measure recommendations are ranked by.
+    pub fn set_rank_by(&mut self, rank_by: RankBy) {
+        self.rank_by = rank_by;
+    }
+
+    // In hard mode, only recommend guesses consistent with every score so far (words which could be the answer).
+    pub fn set_hard_mode(&mut self, hard_mode: bool) {
+        self.hard_mode = hard_mode;
+    }
+
+    // Weight the answers by how likely each is to be chosen, for ranking candidates and recommendations.
+    pub fn set_priors(&mut self, priors: &Priors) {
+        self.weights = (!priors.is_uniform()).then(|| priors.weights(self.matrix.answers()));
+        self.sort_candidates();
+    }
+
+    // List the earlier turns a guess would break in hard mode. Empty if the guess is allowed.
+    pub fn hard_mode_violations(&self, guess: &str) -> Vec<Violation> {
+        hard_mode_violations(self.matrix.scorer(), &self.history, guess)
+    }
+
+    pub fn candidates(&self) -> Vec<&'a str> {
+        self.candidates.iter().map(|a| self.matrix.answers()[*a]).collect()
+    }
+
+    // The chance each remaining candidate is the answer, in the same order as candidates().
+    pub fn probabilities(&self) -> Vec<f64> {
+        let weights = self.candidates.iter().map(|a| self.weights.as_ref().map_or(1.0, |w| w[*a])).collect::<Vec<_>>();
+        let total = weights.iter().sum::<f64>();
+        weights.iter().map(|weight| weight / total).collect()
+    }
+
+    // The remaining candidates, as indices into the matrix answers.
+    pub fn candidate_indices(&self) -> &[usize] {
+        &self.candidates
+    }
+
+    pub fn history(&self) -> &[(String, Score)] {
+        &self.history
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.candidates.len() == 1
+    }
+
+    // Record a guess and the score seen for it, removing every candidate which would have scored differently.
+    pub fn add_turn(&mut self, guess: &str, score: Score) -> Result<(), QwertleError> {
+        let matrix = self.matrix;
+        let guess = match matrix.answers().first() {
+            Some(answer) => validate_word_length(guess, answer.len())?,
+            None => validate_word(guess)?,
+        };
+        validate_score(&guess, &score)?;
+
+        self.candidates.retain(|a| matrix.scorer().score_words(&guess, matrix.answers()[*a]) == score);
+        self.history.push((guess, score));
+        Ok(())
+    }
+
+    // Keep the most likely candidates first. Equally likely candidates stay in answer order.
+    fn sort_candidates(&mut self) {
+        self.candidates.sort();
+        if let Some(weights) = self.weights.as_ref() {
+            self.candidates.sort_by(|a, b| weights[*b].total_cmp(&weights[*a]));
+        }
+    }
+
+    // Rate every guess (only those allowed, in hard mode) against the remaining candidates, best first.
+    pub fn recommendations(&self) -> Vec<GuessRating<'a>> {
+        let allowed = (0..self.matrix.guesses().len())
+            .filter(|g| !self.hard_mode || is_hard_mode_guess(self.matrix.scorer(), &self.history, self.matrix.guesses()[*g]))
+            .collect::<Vec<_>>();
+
+        rank_guess_subset(self.matrix, &allowed, &self.candidates, self.weights.as_deref(), self.rank_by)
+    }
+
+    // Recommend the next guess: the best ranked guess, or just the most likely answer once only one or two remain.
+    pub fn recommend(&self) -> Option<&'a str> {
+        if self.candidates.len() <= 2 {
+            return self.candidates.first().map(|a| self.matrix.answers()[*a]);
+        }
+
+        self.recommendations().first().map(|rating| rating.guess)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{*, answers::ANSWERS, dictionary::Dictionary, distance::Scorer};
+    use super::*;
+
+    fn score(text: &str) -> Score {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn solver_narrows_candidates() {
+        let matrix = ScoreMatrix::build(Scorer::qwertle(), ANSWERS, ANSWERS).unwrap();
+        let mut solver = Solver::new(&matrix);
+        assert_eq!(2315, solver.candidates().len());
+        assert!(!solver.is_solved());
+
+        // 'apple' scores 42521 against 'vivid' and nothing else
+        solver.add_turn("APPLE", score("42521")).unwrap();
+        assert_eq!(vec!["vivid"], solver.candidates());
+        assert!(solver.is_solved());
+        assert_eq!(Some("vivid"), solver.recommend());
+        assert_eq!(vec![("apple".to_string(), score("42521"))], solver.history());
+    }
+
+    #[test]
+    fn solver_intersects_turns() {
+        let matrix = ScoreMatrix::build(Scorer::qwertle(), ANSWERS, ANSWERS).unwrap();
+        let mut solver = Solver::new(&matrix);
+
+        // Every answer at distance 1 of 'rigid' for 'apple' (see answer_options)
+        solver.add_turn("apple", score("32521")).unwrap();
+        let after_first = solver.candidates().to_vec();
+        assert!(after_first.contains(&"rigid"));
+
+        for answer in after_first.iter() {
+            assert_eq!(score("32521"), word_distance("apple", answer).unwrap());
+        }
+
+        solver.add_turn("rigid", score("00000")).unwrap();
+        assert_eq!(vec!["rigid"], solver.candidates());
+    }
+
+    #[test]
+    fn solver_with_guesses() {
+        let dictionary = Dictionary::embedded();
+        let guesses = dictionary.guesses();
+        let matrix = ScoreMatrix::build(Scorer::qwertle(), &guesses, ANSWERS).unwrap();
+        let mut solver = Solver::new(&matrix);
+
+        solver.add_turn("papal", score("85931")).unwrap();
+        assert_eq!(vec!["shack", "shark", "sharp", "whack"], solver.candidates());
+
+        // Recommendations come from the full guess list, but prefer possible answers when tied
+        assert_eq!(guesses.len(), solver.recommendations().len());
+        assert_eq!(Some("shack"), solver.recommend());
+    }
+
+    #[test]
+    fn solver_recommends_splitting_guess() {
+        let matrix = ScoreMatrix::build(Scorer::qwertle(), ANSWERS, ANSWERS).unwrap();
+        let mut solver = Solver::new(&matrix);
+        solver.add_turn("papal", score("50365")).unwrap();
+        assert_eq!(vec!["gamut", "gaunt", "taunt", "vaunt"], solver.candidates());
+
+        // The recommendation should separate all four remaining answers
+        let next = solver.recommend().unwrap();
+        assert_eq!(4, word_distance_map(next, &solver.candidates()).unwrap().len());
+
+        // With two left, guess one of them
+        solver.add_turn("taunt", word_distance("taunt", "gaunt").unwrap()).unwrap();
+        assert_eq!(vec!["gaunt"], solver.candidates());
+
+        // No candidates remain if the scores are inconsistent
+        solver.add_turn("gaunt", score("11111")).unwrap();
+        assert!(solver.candidates().is_empty());
+        assert_eq!(None, solver.recommend());
+    }
+
+    #[test]
+    fn solver_hard_mode() {
+        let matrix = ScoreMatrix::build(Scorer::qwertle(), ANSWERS, ANSWERS).unwrap();
+        let mut solver = Solver::new(&matrix);
+        solver.set_hard_mode(true);
+
+        // Before any turns, every guess is allowed
+        assert_eq!(ANSWERS.len(), solver.recommendations().len());
+
+        solver.add_turn("papal", score("00265")).unwrap();
+        assert!(solver.hard_mode_violations("paint").is_empty());
+        assert_eq!(1, solver.hard_mode_violations("salal").len());
+
+        // Only guesses consistent with the score are recommended
+        let recommendations = solver.recommendations();
+        assert!(!recommendations.is_empty());
+        assert!(recommendations.iter().all(|rating| word_distance("papal", rating.guess).unwrap() == score("00265")));
+    }
+
+    #[test]
+    fn solver_priors() {
+        let matrix = ScoreMatrix::build(Scorer::qwertle(), ANSWERS, ANSWERS).unwrap();
+        let mut solver = Solver::new(&matrix);
+        solver.add_turn("papal", score("50365")).unwrap();
+        assert_eq!(vec![0.25; 4], solver.probabilities());
+
+        // 'vaunt' is rare and 'taunt' is common: the likely answers come first
+        solver.set_priors(&Priors::parse("gamut 20\ngaunt 10\ntaunt 60\nvaunt 1\n").unwrap());
+        assert_eq!(vec!["taunt", "gamut", "gaunt", "vaunt"], solver.candidates());
+
+        let probabilities = solver.probabilities();
+        assert!((probabilities[0] - 60.0 / 91.0).abs() < 1e-9);
+
+        // The best guess still separates all four, but the entropy reflects the uneven odds
+        let best = &solver.recommendations()[0];
+        assert_eq!(1, best.worst_case);
+        assert!(best.entropy < 2.0);
+
+        // Once only two remain, guess the more likely one
+        solver.add_turn("gaunt", word_distance("gaunt", "taunt").unwrap()).unwrap();
+        assert_eq!(Some("taunt"), solver.recommend());
+    }
+}
@@ -0,0 +1,203 @@
+use std::collections::BTreeMap;
+use serde::Serialize;
+use crate::{error::*, matrix::ScoreMatrix, recommend::*};
+
+// A complete strategy: the word to guess, and the subtree to follow for each score it could get.
+// When the guess could be the answer (is_answer), the winning score (00000) has no subtree.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DecisionTree {
+    pub guess: String,
+    pub is_answer: bool,
+
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub children: BTreeMap<String, DecisionTree>,
+}
+
+// How many guesses a strategy tree needs to solve each answer.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TreeStats {
+    pub answers: usize,
+    pub max_guesses: usize,
+    pub average_guesses: f64,
+
+    // Entry H[i] is how many answers are solved on guess i+1.
+    pub histogram: Vec<usize>,
+}
+
+impl DecisionTree {
+    // Build a strategy tree which opens with a fixed guess, then picks each later guess by ranking
+    // the guesses in the matrix against the answers still possible.
+    // Fails if a guess scores as solved for more than one answer, which happens when the answers have duplicates
+    // or words of different lengths, since the tree couldn't tell those answers apart.
+    pub fn build(matrix: &ScoreMatrix, opener: &str, by: RankBy) -> Result<DecisionTree, QwertleError> {
+        let answers = (0..matrix.answers().len()).collect::<Vec<_>>();
+        DecisionTree::build_from(matrix, opener, &answers, by)
+    }
+
+    // Build the subtree for a guess which solves the given candidates (answer indices).
+    fn build_from(matrix: &ScoreMatrix, guess: &str, candidates: &[usize], by: RankBy) -> Result<DecisionTree, QwertleError> {
+        let map = matrix.group_by_score(guess, candidates);
+        let mut is_answer = false;
+        let mut children = BTreeMap::new();
+
+        for (score, group) in map {
+            if score.is_solved() {
+                if group.len() > 1 {
+                    let words = group.iter().map(|a| matrix.answers()[*a]).collect::<Vec<_>>();
+                    return Err(QwertleError::InvalidInput(format!("'{guess}' scores {score} (solved) for {} answers: {}. Check the answers have no duplicates and are all the same length.", words.len(), words.join(", "))));
+                }
+
+                is_answer = true;
+                continue;
+            }
+
+            // With one or two candidates left, guess one of them. If the best ranked guess can't split the
+            // candidates (every score is the same), guess them one at a time instead, so each guess makes progress.
+            let mut next = if group.len() <= 2 {
+                matrix.answers()[group[0]]
+            } else {
+                rank_guesses(matrix, &group, by)[0].guess
+            };
+
+            if !splits(matrix, next, &group) {
+                next = matrix.answers()[group[0]];
+            }
+
+            children.insert(score.to_string(), DecisionTree::build_from(matrix, next, &group, by)?);
+        }
+
+        Ok(DecisionTree { guess: guess.to_string(), is_answer, children })
+    }
+
+    // Find how many guesses the tree needs for each answer it covers.
+    pub fn guess_counts(&self) -> Vec<(String, usize)> {
+        let mut counts = Vec::new();
+        self.add_guess_counts(1, &mut counts);
+        counts.sort();
+        counts
+    }
+
+    fn add_guess_counts(&self, depth: usize, counts: &mut Vec<(String, usize)>) {
+        if self.is_answer {
+            counts.push((self.guess.clone(), depth));
+        }
+
+        for child in self.children.values() {
+            child.add_guess_counts(depth + 1, counts);
+        }
+    }
+
+    // Summarize the number of guesses needed across every answer.
+    pub fn stats(&self) -> TreeStats {
+        let counts = self.guess_counts();
+        let mut histogram = Vec::new();
+
+        for (_, guesses) in counts.iter() {
+            while histogram.len() < *guesses {
+                histogram.push(0);
+            }
+
+            histogram[guesses - 1] += 1;
+        }
+
+        let total = counts.iter().map(|(_, guesses)| guesses).sum::<usize>();
+
+        TreeStats {
+            answers: counts.len(),
+            max_guesses: histogram.len(),
+            average_guesses: if counts.is_empty() { 0.0 } else { total as f64 / counts.len() as f64 },
+            histogram,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+// Whether a guess tells any of the candidates apart, or solves them. A guess which gives every candidate the same
+// unsolved score would leave the same candidates to guess again.
+pub(crate) fn splits(matrix: &ScoreMatrix, guess: &str, candidates: &[usize]) -> bool {
+    let map = matrix.group_by_score(guess, candidates);
+    map.len() > 1 || map.keys().any(|score| score.is_solved())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{answers::ANSWERS, distance::Scorer};
+    use super::*;
+
+    #[test]
+    fn small_tree() {
+        let answers = ["gamut", "gaunt", "taunt", "vaunt"];
+        let matrix = ScoreMatrix::build(Scorer::qwertle(), &answers, &answers).unwrap();
+        let tree = DecisionTree::build(&matrix, "taunt", RankBy::Entropy).unwrap();
+
+        assert_eq!("taunt", tree.guess);
+        assert_eq!(3, tree.children.len());
+        assert!(tree.children.values().all(|child| child.children.is_empty()));
+
+        let stats = tree.stats();
+        assert_eq!(4, stats.answers);
+        assert_eq!(2, stats.max_guesses);
+        assert_eq!(vec![1, 3], stats.histogram);
+        assert_eq!(1.75, stats.average_guesses);
+
+        let json = tree.to_json();
+        assert!(json.starts_with("{\n  \"guess\": \"taunt\",\n  \"is_answer\": true,\n  \"children\": {"));
+    }
+
+    #[test]
+    fn opener_not_an_answer() {
+        // When the opener can't be the answer, every answer needs at least two guesses
+        let answers = ["gamut", "gaunt", "taunt", "vaunt"];
+        let matrix = ScoreMatrix::build(Scorer::qwertle(), &answers, &answers).unwrap();
+        let tree = DecisionTree::build(&matrix, "papal", RankBy::Entropy).unwrap();
+        assert!(!tree.is_answer);
+        assert_eq!(1, tree.children.len());
+
+        let stats = tree.stats();
+        assert_eq!(4, stats.answers);
+        assert_eq!(0, stats.histogram[0]);
+    }
+
+    #[test]
+    fn full_tree_solves_every_answer() {
+        let matrix = ScoreMatrix::build(Scorer::qwertle(), ANSWERS, ANSWERS).unwrap();
+        let tree = DecisionTree::build(&matrix, "papal", RankBy::Entropy).unwrap();
+        let counts = tree.guess_counts();
+        assert_eq!(ANSWERS.len(), counts.len());
+
+        let stats = tree.stats();
+        assert_eq!(ANSWERS.len(), stats.histogram.iter().sum::<usize>());
+        assert!(stats.max_guesses <= 4);
+        assert!(stats.average_guesses < 2.2);
+    }
+
+    #[test]
+    fn guesses_which_dont_split() {
+        // 'a' and 'w' are both one away from 'q', so the only allowed guess scores every answer 11 and can't
+        // split them. The tree guesses the answers themselves instead of repeating 'qq' forever.
+        let answers = ["aa", "aw", "wa", "ww"];
+        let matrix = ScoreMatrix::build(Scorer::qwertle(), &["qq"], &answers).unwrap();
+        let tree = DecisionTree::build(&matrix, "qq", RankBy::Entropy).unwrap();
+        assert_eq!(vec!["11"], tree.children.keys().collect::<Vec<_>>());
+        assert_eq!("aa", tree.children["11"].guess);
+
+        let stats = tree.stats();
+        assert_eq!(4, stats.answers);
+        assert_eq!(0, stats.histogram[0]);
+    }
+
+    #[test]
+    fn answers_sharing_solved_score() {
+        let answers = ["aunt", "aunt"];
+        let matrix = ScoreMatrix::build(Scorer::qwertle(), &answers, &answers).unwrap();
+        let error = DecisionTree::build(&matrix, "aunt", RankBy::Entropy).unwrap_err();
+        assert!(error.to_string().contains("for 2 answers: aunt, aunt."));
+
+        // A shorter guess would score as solved for a longer word which starts with it, so the matrix rejects mixed lengths
+        let answers = ["pap", "papal"];
+        assert!(ScoreMatrix::build(Scorer::qwertle(), &answers, &answers).is_err());
+    }
+}
@@ -0,0 +1,312 @@
+use std::{collections::BTreeMap, io::{self, BufRead, Write}};
+use crate::{distance::Scorer, error::validate_word, score::{is_separator, Score}};
+
+// Background colors for each distance, green (the right letter) through yellow to red (far away),
+// like the tile gradient in QWERTLE. These are xterm 256-color codes.
+const GRADIENT: [u8; 10] = [46, 82, 118, 154, 190, 226, 220, 214, 208, 196];
+
+// How many of the remaining answers to list after each tile.
+const SHOW_LIMIT: usize = 10;
+
+// Draw the keyboard, with each key showing its distance from a letter ('from') and colored by that distance.
+// Keys are placed by their pixel position on the layout, ten pixels to a column.
+pub fn render_keyboard(scorer: &Scorer, from: char, color: bool) -> String {
+    let mut rows: BTreeMap<u16, Vec<(u16, char)>> = BTreeMap::new();
+    for (letter, (x, y)) in ('a'..='z').zip(scorer.layout().positions.iter()) {
+        rows.entry(*y).or_default().push((*x, letter));
+    }
+
+    let mut text = String::new();
+    for keys in rows.values_mut() {
+        keys.sort();
+
+        let mut column = 0;
+        for (x, letter) in keys.iter() {
+            let start = (*x / 10) as usize;
+            while column < start {
+                text += " ";
+                column += 1;
+            }
+
+            let distance = scorer.letter_distance(from, *letter);
+            let label = format!(" {}{} ", letter.to_ascii_uppercase(), distance);
+
+            if color {
+                text += &format!("\x1b[30;48;5;{}m{label}\x1b[0m", GRADIENT[(distance as usize).min(9)]);
+            } else {
+                text += &label;
+            }
+
+            column += label.len();
+        }
+
+        text += "\n";
+    }
+
+    text
+}
+
+// Draw a guess as tiles, each showing its letter and distance, colored by the distance.
+pub fn render_score(guess: &str, score: &Score, color: bool) -> String {
+    let mut text = String::new();
+
+    for (letter, distance) in guess.chars().zip(score.digits().iter()) {
+        let label = format!(" {}{} ", letter.to_ascii_uppercase(), distance);
+
+        if color {
+            text += &format!("\x1b[30;48;5;{}m{label}\x1b[0m ", GRADIENT[(*distance as usize).min(9)]);
+        } else {
+            text += &format!("{label} ");
+        }
+    }
+
+    text.trim_end().to_string()
+}
+
+// Helps during a game: for each guess, shows the keyboard colored by distance from each guess letter,
+// asks for the tile's distance, and narrows the answers as each tile is entered.
+pub struct Assistant<'a> {
+    scorer: Scorer,
+    candidates: Vec<&'a str>,
+    color: bool,
+}
+
+impl<'a> Assistant<'a> {
+    pub fn new(scorer: &Scorer, answers: &[&'a str], color: bool) -> Assistant<'a> {
+        Assistant { scorer: scorer.clone(), candidates: answers.to_vec(), color }
+    }
+
+    pub fn candidates(&self) -> &[&'a str] {
+        &self.candidates
+    }
+
+    // Run until the answer is found, the input ends, or a blank guess is entered.
+    pub fn run(&mut self, input: &mut impl BufRead, output: &mut impl Write) -> io::Result<()> {
+        let word_length = self.candidates.first().map(|word| word.len());
+
+        loop {
+            let guess = match prompt(input, output, "\nGuess (blank to quit): ")? {
+                Some(line) if !line.is_empty() => line.to_ascii_lowercase(),
+                _ => return Ok(()),
+            };
+
+            // Checking the guess also keeps it to MAX_WORD_LENGTH letters, so its tiles always fit in a Score
+            if validate_word(&guess).is_err() || word_length.is_some_and(|length| length != guess.len()) {
+                writeln!(output, "Enter a {}-letter word.", word_length.unwrap_or(5))?;
+                continue;
+            }
+
+            let score = match self.read_score(&guess, input, output)? {
+                Some(score) => score,
+                None => return Ok(()),
+            };
+
+            if self.show_turn(&guess, &score, output)? {
+                return Ok(());
+            }
+        }
+    }
+
+    // Ask for the distance of each tile in turn, showing the keyboard for the tile's letter and the answers
+    // matching the tiles so far. Enter 'b' to go back a tile. Distances may be more than one digit (ex: 11), and
+    // several tiles can be entered at once, split by the separators scores use (ex: '3 11 2' or '3.11.2').
+    fn read_score(&self, guess: &str, input: &mut impl BufRead, output: &mut impl Write) -> io::Result<Option<Score>> {
+        let letters = guess.chars().collect::<Vec<_>>();
+        let mut digits: Vec<u8> = Vec::new();
+
+        while digits.len() < letters.len() {
+            let letter = letters[digits.len()];
+            writeln!(output, "\nTile {} of {}: '{}'", digits.len() + 1, letters.len(), letter.to_ascii_uppercase())?;
+            write!(output, "{}", render_keyboard(&self.scorer, letter, self.color))?;
+
+            let line = match prompt(input, output, "Distance (several split by spaces, b to go back): ")? {
+                Some(line) => line,
+                None => return Ok(None),
+            };
+
+            match line.as_str() {
+                "b" | "B" => {
+                    digits.pop();
+                    continue;
+                }
+                _ => match parse_tiles(&line) {
+                    Some(tiles) if digits.len() + tiles.len() <= letters.len() => digits.extend(tiles),
+                    Some(_) => {
+                        writeln!(output, "Only {} tiles are left.", letters.len() - digits.len())?;
+                        continue;
+                    }
+                    None => {
+                        writeln!(output, "Enter a distance, like 3 or 11.")?;
+                        continue;
+                    }
+                },
+            }
+
+            // Show the answers consistent with the tiles entered so far
+            let matching = self.candidates.iter()
+                .filter(|answer| self.scorer.score_words(guess, answer).digits()[..digits.len()] == digits[..])
+                .collect::<Vec<_>>();
+
+            let shown = matching.iter().take(SHOW_LIMIT).map(|a| a.to_string()).collect::<Vec<_>>();
+            let more = if matching.len() > SHOW_LIMIT { ", ..." } else { "" };
+            writeln!(output, "{} answers match {}: {}{more}", matching.len(), Score::from_distances(digits.iter().copied()), shown.join(", "))?;
+        }
+
+        Ok(Some(Score::from_distances(digits.iter().copied())))
+    }
+
+    // Show the answers closest to the score, and keep only the exact matches for later turns.
+    // Returns whether the game is solved.
+    fn show_turn(&mut self, guess: &str, score: &Score, output: &mut impl Write) -> io::Result<bool> {
+        let options = self.scorer.closest_answers(guess, score, &self.candidates, 2);
+        let exact = options.iter().filter(|(distance, _, _)| *distance == 0).map(|(_, answer, _)| *answer).collect::<Vec<_>>();
+
+        writeln!(output, "\n{guess} {score}:")?;
+        for (distance, answer, answer_score) in options.iter().take(SHOW_LIMIT) {
+            writeln!(output, "  {distance}: {answer} ({answer_score})")?;
+        }
+
+        if score.is_solved() {
+            if !self.candidates.contains(&guess) {
+                writeln!(output, "'{guess}' scored {score} (solved), but isn't one of the remaining answers; check the tiles and enter the guess again.")?;
+                return Ok(false);
+            }
+
+            self.candidates = exact;
+            writeln!(output, "Solved: {guess}")?;
+            return Ok(true);
+        }
+
+        if exact.is_empty() {
+            writeln!(output, "No answers match {score} exactly; check the tiles and enter the guess again.")?;
+            return Ok(false);
+        }
+
+        self.candidates = exact;
+        if self.candidates.len() == 1 {
+            writeln!(output, "Solved: {}", self.candidates[0])?;
+            return Ok(true);
+        }
+
+        writeln!(output, "{} answers remain.", self.candidates.len())?;
+        Ok(false)
+    }
+}
+
+// Read the distances of one or more tiles, split by any score separator. None if any isn't a number.
+fn parse_tiles(line: &str) -> Option<Vec<u8>> {
+    let tiles = line.split(is_separator).filter(|part| !part.is_empty()).map(|part| part.parse::<u8>().ok()).collect::<Option<Vec<_>>>()?;
+    if tiles.is_empty() { None } else { Some(tiles) }
+}
+
+// Write a prompt and read a trimmed line, or None at the end of input.
+fn prompt(input: &mut impl BufRead, output: &mut impl Write, text: &str) -> io::Result<Option<String>> {
+    write!(output, "{text}")?;
+    output.flush()?;
+
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+
+    Ok(Some(line.trim().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{*, answers::ANSWERS, distance::parse_model, keyboard::QWERTY};
+    use super::*;
+
+    #[test]
+    fn keyboard_rows() {
+        let keyboard = render_keyboard(Scorer::qwertle(), 'p', false);
+        let rows = keyboard.lines().collect::<Vec<_>>();
+        assert_eq!(3, rows.len());
+
+        // 'P' is the last key on the top row, at distance zero from itself; 'Q' is furthest
+        assert!(rows[0].starts_with(" Q9   W8 "));
+        assert!(rows[0].ends_with(" P0 "));
+
+        // Rows are staggered like the keyboard
+        assert!(rows[1].starts_with("    A"));
+        assert!(rows[2].starts_with("         Z"));
+
+        let colored = render_keyboard(Scorer::qwertle(), 'p', true);
+        assert!(colored.contains("\x1b[30;48;5;46m P0 \x1b[0m"));
+
+        // Distances past 9 are shown in full
+        let scorer = Scorer::new(QWERTY, parse_model("euclidean:20").unwrap()).unwrap();
+        let keyboard = render_keyboard(&scorer, 'p', false);
+        assert!(keyboard.starts_with(&format!(" Q{} ", scorer.letter_distance('p', 'q'))));
+        assert!(keyboard.contains(" Q23 "));
+    }
+
+    #[test]
+    fn score_tiles() {
+        let score = word_distance("papal", "paint").unwrap();
+        assert_eq!(" P0   A0   P2   A6   L5", render_score("papal", &score, false));
+        assert!(render_score("papal", &score, true).starts_with("\x1b[30;48;5;46m P0 \x1b[0m "));
+    }
+
+    #[test]
+    fn assist_session() {
+        let mut assistant = Assistant::new(Scorer::qwertle(), ANSWERS, false);
+        let mut input = "apple\n3\n2\nx\n5\n2\nb\n2\n1\nrigid\n0\n0\n0\n0\n0\n".as_bytes();
+        let mut output = Vec::new();
+        assistant.run(&mut input, &mut output).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("Tile 1 of 5: 'A'"));
+        assert!(output.contains("Enter a distance, like 3 or 11."));
+        assert!(output.contains(&format!("  0: rigid ({})", word_distance("apple", "rigid").unwrap())));
+        assert!(output.contains("Solved: rigid"));
+        assert_eq!(vec!["rigid"], assistant.candidates());
+    }
+
+    #[test]
+    fn assist_rejects_bad_guesses() {
+        let mut assistant = Assistant::new(Scorer::qwertle(), ANSWERS, false);
+        let mut input = "app\n\n".as_bytes();
+        let mut output = Vec::new();
+        assistant.run(&mut input, &mut output).unwrap();
+
+        assert!(String::from_utf8(output).unwrap().contains("Enter a 5-letter word."));
+        assert_eq!(ANSWERS.len(), assistant.candidates().len());
+    }
+
+    #[test]
+    fn assist_multi_digit_tiles() {
+        let scorer = Scorer::new(QWERTY, parse_model("euclidean:20").unwrap()).unwrap();
+        let score = scorer.score_words("qqqqq", "rigid");
+        assert!(score.digits().iter().any(|digit| *digit > 9));
+
+        // Enter the first two tiles one at a time, then the rest on one line split by dots, with too many first
+        let digits = score.digits().iter().map(|digit| digit.to_string()).collect::<Vec<_>>();
+        let text = format!("qqqqq\n{}\n{}\n1.2.3.4\n{}\n", digits[0], digits[1], digits[2..].join("."));
+
+        let mut assistant = Assistant::new(&scorer, ANSWERS, false);
+        let mut output = Vec::new();
+        assistant.run(&mut text.as_bytes(), &mut output).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("Only 3 tiles are left."));
+        assert!(output.contains(&format!("qqqqq {score}:")));
+        assert!(assistant.candidates().contains(&"rigid"));
+        assert_eq!(Some(vec![3, 11, 2]), parse_tiles("3 11,2"));
+        assert_eq!(None, parse_tiles("3 x"));
+    }
+
+    #[test]
+    fn assist_solved_guess_not_an_answer() {
+        // 'qqqqq' isn't an answer, so all zeros for it is a mistake rather than a solve
+        let mut assistant = Assistant::new(Scorer::qwertle(), ANSWERS, false);
+        let mut input = "qqqqq\n0 0 0 0 0\n\n".as_bytes();
+        let mut output = Vec::new();
+        assistant.run(&mut input, &mut output).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("'qqqqq' scored 00000 (solved), but isn't one of the remaining answers"));
+        assert!(!output.contains("Solved:"));
+        assert_eq!(ANSWERS.len(), assistant.candidates().len());
+    }
+}
@@ -0,0 +1,136 @@
+use std::{collections::HashMap, sync::OnceLock};
+use crate::{*, answers::ANSWERS, matrix::ScoreMatrix, output::*, priors::Priors, solver::Solver};
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::prelude::*;
+
+// JavaScript bindings, so a browser helper can run the solver client-side. Build with:
+//  cargo build --release --lib --target wasm32-unknown-unknown
+//  wasm-bindgen --target web --out-dir pkg target/wasm32-unknown-unknown/release/qwertle.wasm
+// Everything uses the QWERTLE keyboard and the built-in answers. Results are JSON, like the server's responses,
+// and errors are thrown as their message strings. The bindings are plain functions on other targets, so they're tested natively.
+
+#[cfg(target_arch = "wasm32")]
+impl From<QwertleError> for JsValue {
+    fn from(error: QwertleError) -> JsValue {
+        JsValue::from_str(&error.to_string())
+    }
+}
+
+// The score digits for a guess against an answer. Ex: wordDistance("apple", "vivid") is "42521".
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen(js_name = wordDistance))]
+pub fn web_word_distance(guess: &str, answer: &str) -> Result<String, QwertleError> {
+    Ok(word_distance(guess, answer)?.to_string())
+}
+
+// The answers whose score for the guess is within 'within' of the score seen (see answer_options_report).
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen(js_name = answerOptions))]
+pub fn web_answer_options(guess: &str, score: &str, within: u32) -> Result<String, QwertleError> {
+    let report = answer_options_report(Scorer::qwertle(), &validate_word(guess)?, &score.parse()?, ANSWERS, within, &Priors::uniform());
+    Ok(to_json(&report))
+}
+
+// The letters each guess letter could be for the score, most common first (see letter_options_report).
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen(js_name = letterOptions))]
+pub fn web_letter_options(guess: &str, score: &str) -> Result<String, QwertleError> {
+    static FREQUENCIES: OnceLock<HashMap<(char, u8), u16>> = OnceLock::new();
+    let frequencies = FREQUENCIES.get_or_init(|| letter_frequencies(ANSWERS));
+
+    let report = letter_options_report(Scorer::qwertle(), &validate_word(guess)?, &score.parse()?, frequencies);
+    Ok(to_json(&report))
+}
+
+// The score matrix for the solver, built on first use. Only answers are used as guesses, which keeps it
+// to about ten megabytes and fast to build in the browser.
+fn answers_matrix() -> &'static ScoreMatrix<'static> {
+    static MATRIX: OnceLock<ScoreMatrix<'static>> = OnceLock::new();
+    MATRIX.get_or_init(|| ScoreMatrix::build(Scorer::qwertle(), ANSWERS, ANSWERS).unwrap())
+}
+
+// A game in progress: add each guess and score, then read the remaining answers and recommendations.
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+pub struct WebSolver {
+    solver: Solver<'static>,
+    hard_mode: bool,
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+impl WebSolver {
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen(constructor))]
+    pub fn new(hard_mode: bool) -> WebSolver {
+        let mut solver = Solver::new(answers_matrix());
+        solver.set_hard_mode(hard_mode);
+        WebSolver { solver, hard_mode }
+    }
+
+    // Record a guess and its score. In hard mode, guesses inconsistent with an earlier score are refused.
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen(js_name = addTurn))]
+    pub fn add_turn(&mut self, guess: &str, score: &str) -> Result<(), QwertleError> {
+        let guess = validate_word(guess)?;
+        let score = score.parse::<Score>()?;
+
+        let violations = if self.hard_mode { self.solver.hard_mode_violations(&guess) } else { Vec::new() };
+        if let Some(violation) = violations.first() {
+            return Err(QwertleError::InvalidInput(format!("'{guess}' is not allowed in hard mode. {violation}")));
+        }
+
+        self.solver.add_turn(&guess, score)
+    }
+
+    // The turns, remaining answers, and up to 'limit' recommended guesses, as JSON (see solve_report).
+    pub fn report(&self, limit: usize) -> String {
+        to_json(&solve_report(&self.solver, limit))
+    }
+
+    // The next guess to make, if any answers remain.
+    pub fn recommend(&self) -> Option<String> {
+        self.solver.recommend().map(|guess| guess.to_string())
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen(js_name = isSolved))]
+    pub fn is_solved(&self) -> bool {
+        self.solver.is_solved()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+    use super::*;
+
+    #[test]
+    fn web_functions() {
+        assert_eq!(Ok("42521".to_string()), web_word_distance("APPLE", "vivid"));
+        assert!(web_word_distance("app le", "vivid").is_err());
+        assert!(web_word_distance("apple", "vivids").is_err());
+
+        let json: Value = serde_json::from_str(&web_answer_options("apple", "42521", 1).unwrap()).unwrap();
+        assert_eq!("vivid", json["answers"][0]["answer"]);
+        assert_eq!(3, json["answers"].as_array().unwrap().len());
+        assert!(web_answer_options("apple", "4x521", 1).is_err());
+
+        let json: Value = serde_json::from_str(&web_letter_options("apple", "42521").unwrap()).unwrap();
+        assert_eq!("tgv", json["positions"][0]["options"]);
+    }
+
+    #[test]
+    fn web_solver() {
+        let mut solver = WebSolver::new(true);
+        assert!(solver.add_turn("papal", "50365").is_ok());
+        assert!(!solver.is_solved());
+
+        let json: Value = serde_json::from_str(&solver.report(3)).unwrap();
+        assert!(json["candidates"].as_array().unwrap().len() > 1);
+
+        // Hard mode refuses guesses which can't be the answer
+        let error = solver.add_turn("apple", "00000").unwrap_err();
+        assert!(error.to_string().contains("not allowed in hard mode"));
+        assert!(matches!(solver.add_turn("qw3rt", "00000"), Err(QwertleError::InvalidLetter { .. })));
+        assert!(matches!(solver.add_turn("gaunt", "0000"), Err(QwertleError::MalformedScore { .. })));
+
+        let mut solver = WebSolver::new(false);
+        solver.add_turn("apple", "42521").unwrap();
+        assert!(solver.is_solved());
+        assert_eq!(Some("vivid".to_string()), solver.recommend());
+    }
+}